@@ -0,0 +1,76 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::utils::copy_instruction_data;
+
+pub struct InitializeWithData<'a> {
+    /// Payer
+    pub payer: &'a AccountInfo,
+    /// Data account
+    pub data: &'a AccountInfo,
+    /// Entity
+    pub entity: &'a AccountInfo,
+    /// Authority
+    pub authority: &'a AccountInfo,
+    /// Instruction sysvar account
+    pub instruction_sysvar_account: &'a AccountInfo,
+    /// System program
+    pub system_program: &'a AccountInfo,
+    /// Component program
+    pub component_program: &'a Pubkey,
+    /// Initial component data
+    pub instruction_data: &'a [u8],
+}
+
+impl InitializeWithData<'_> {
+    pub const DISCRIMINATOR: [u8; 8] = [229, 231, 179, 149, 108, 220, 109, 150];
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 6] = [
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::writable(self.data.key()),
+            AccountMeta::readonly(self.entity.key()),
+            AccountMeta::readonly(self.authority.key()),
+            AccountMeta::readonly(self.instruction_sysvar_account.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        const DISCRIMATOR_LENGTH: usize = 8;
+
+        let mut instruction_data = [0u8; 256 + DISCRIMATOR_LENGTH];
+
+        let instruction = Instruction {
+            program_id: self.component_program,
+            accounts: &account_metas,
+            data: copy_instruction_data(
+                &mut instruction_data,
+                &Self::DISCRIMINATOR,
+                self.instruction_data,
+            )?,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.payer,
+                self.data,
+                self.entity,
+                self.authority,
+                self.instruction_sysvar_account,
+                self.system_program,
+            ],
+            signers,
+        )
+    }
+}
@@ -4,8 +4,26 @@ pub use destroy::*;
 mod initialize;
 pub use initialize::*;
 
+mod initialize_with_data;
+pub use initialize_with_data::*;
+
+mod resize;
+pub use resize::*;
+
 mod update;
 pub use update::*;
 
+mod update_from_buffer;
+pub use update_from_buffer::*;
+
+mod update_from_buffer_with_session;
+pub use update_from_buffer_with_session::*;
+
+mod update_patch;
+pub use update_patch::*;
+
+mod update_patch_with_session;
+pub use update_patch_with_session::*;
+
 mod update_with_session;
 pub use update_with_session::*;
@@ -0,0 +1,74 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+
+pub struct Resize<'a> {
+    /// Authority
+    pub authority: &'a AccountInfo,
+    /// Payer of rent top-ups and receiver of refunds
+    pub payer: &'a AccountInfo,
+    /// Entity
+    pub entity: &'a AccountInfo,
+    /// Component
+    pub component: &'a AccountInfo,
+    /// Instruction sysvar account
+    pub instruction_sysvar_account: &'a AccountInfo,
+    /// System program
+    pub system_program: &'a AccountInfo,
+    /// Component program
+    pub component_program: &'a Pubkey,
+    /// New size of the component account
+    pub size: u64,
+}
+
+impl Resize<'_> {
+    pub const DISCRIMINATOR: [u8; 8] = [74, 27, 74, 155, 56, 134, 175, 125];
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 6] = [
+            AccountMeta::readonly_signer(self.authority.key()),
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::readonly(self.entity.key()),
+            AccountMeta::writable(self.component.key()),
+            AccountMeta::readonly(self.instruction_sysvar_account.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        const DISCRIMATOR_LENGTH: usize = 8;
+
+        let mut instruction_data = [0u8; DISCRIMATOR_LENGTH + 8];
+
+        instruction_data[0..DISCRIMATOR_LENGTH].copy_from_slice(Self::DISCRIMINATOR.as_slice());
+        instruction_data[DISCRIMATOR_LENGTH..].copy_from_slice(&self.size.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.component_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.authority,
+                self.payer,
+                self.entity,
+                self.component,
+                self.instruction_sysvar_account,
+                self.system_program,
+            ],
+            signers,
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+/// `UpdateFromBuffer` signed through a session token.
+pub struct UpdateFromBufferWithSession<'a> {
+    /// Component
+    pub component: &'a AccountInfo,
+    /// Authority
+    pub authority: &'a AccountInfo,
+    /// Instruction sysvar account
+    pub instruction_sysvar_account: &'a AccountInfo,
+    /// Session token
+    pub session_token: &'a AccountInfo,
+    /// Buffer holding the new state
+    pub buffer: &'a AccountInfo,
+    /// Component program
+    pub component_program: &'a Pubkey,
+    /// Start of the state in the buffer
+    pub offset: u32,
+    /// Length of the state
+    pub len: u32,
+}
+
+impl UpdateFromBufferWithSession<'_> {
+    pub const DISCRIMINATOR: [u8; 8] = [149, 145, 92, 11, 142, 151, 49, 37];
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 5] = [
+            AccountMeta::writable(self.component.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+            AccountMeta::readonly(self.instruction_sysvar_account.key()),
+            AccountMeta::readonly(self.session_token.key()),
+            AccountMeta::readonly(self.buffer.key()),
+        ];
+
+        const DISCRIMATOR_LENGTH: usize = 8;
+
+        let mut instruction_data = [0u8; DISCRIMATOR_LENGTH + 8];
+
+        instruction_data[0..DISCRIMATOR_LENGTH].copy_from_slice(Self::DISCRIMINATOR.as_slice());
+        instruction_data[DISCRIMATOR_LENGTH..DISCRIMATOR_LENGTH + 4]
+            .copy_from_slice(&self.offset.to_le_bytes());
+        instruction_data[DISCRIMATOR_LENGTH + 4..].copy_from_slice(&self.len.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: self.component_program,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.component,
+                self.authority,
+                self.instruction_sysvar_account,
+                self.session_token,
+                self.buffer,
+            ],
+            signers,
+        )
+    }
+}
@@ -0,0 +1,66 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::utils::copy_instruction_data;
+
+/// Update writing `(offset, bytes)` patches over the component data instead of
+/// replacing it. `instruction_data` is a Borsh `Vec<(u32, Vec<u8>)>`.
+pub struct UpdatePatch<'a> {
+    /// Component
+    pub component: &'a AccountInfo,
+    /// Authority
+    pub authority: &'a AccountInfo,
+    /// Instruction sysvar account
+    pub instruction_sysvar_account: &'a AccountInfo,
+    /// Instruction
+    pub component_program: &'a Pubkey,
+    /// Component program
+    pub instruction_data: &'a [u8],
+}
+
+impl UpdatePatch<'_> {
+    pub const DISCRIMINATOR: [u8; 8] = [116, 20, 119, 231, 40, 167, 95, 213];
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 3] = [
+            AccountMeta::writable(self.component.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+            AccountMeta::readonly(self.instruction_sysvar_account.key()),
+        ];
+
+        const DISCRIMATOR_LENGTH: usize = 8;
+
+        let mut instruction_data = [0u8; 256 + DISCRIMATOR_LENGTH];
+
+        let instruction = Instruction {
+            program_id: self.component_program,
+            accounts: &account_metas,
+            data: copy_instruction_data(
+                &mut instruction_data,
+                &Self::DISCRIMINATOR,
+                self.instruction_data,
+            )?,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.component,
+                self.authority,
+                self.instruction_sysvar_account,
+            ],
+            signers,
+        )
+    }
+}
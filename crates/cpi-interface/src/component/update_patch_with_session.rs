@@ -0,0 +1,69 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::utils::copy_instruction_data;
+
+/// `UpdatePatch` signed through a session token.
+pub struct UpdatePatchWithSession<'a> {
+    /// Component
+    pub component: &'a AccountInfo,
+    /// Authority
+    pub authority: &'a AccountInfo,
+    /// Instruction sysvar account
+    pub instruction_sysvar_account: &'a AccountInfo,
+    /// Session token
+    pub session_token: &'a AccountInfo,
+    /// Component program
+    pub component_program: &'a Pubkey,
+    /// Instruction
+    pub instruction_data: &'a [u8],
+}
+
+impl UpdatePatchWithSession<'_> {
+    pub const DISCRIMINATOR: [u8; 8] = [94, 119, 223, 153, 119, 19, 146, 180];
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 4] = [
+            AccountMeta::writable(self.component.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+            AccountMeta::readonly(self.instruction_sysvar_account.key()),
+            AccountMeta::readonly(self.session_token.key()),
+        ];
+
+        const DISCRIMATOR_LENGTH: usize = 8;
+
+        let mut instruction_data = [0u8; 256 + DISCRIMATOR_LENGTH];
+
+        let instruction = Instruction {
+            program_id: self.component_program,
+            accounts: &account_metas,
+            data: copy_instruction_data(
+                &mut instruction_data,
+                &Self::DISCRIMINATOR,
+                self.instruction_data,
+            )?,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.component,
+                self.authority,
+                self.instruction_sysvar_account,
+                self.session_token,
+            ],
+            signers,
+        )
+    }
+}
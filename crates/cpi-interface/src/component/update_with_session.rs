@@ -6,6 +6,8 @@ use pinocchio::{
     ProgramResult,
 };
 
+use crate::utils::copy_instruction_data;
+
 pub struct UpdateWithSession<'a> {
     /// Component
     pub component: &'a AccountInfo,
@@ -42,14 +44,14 @@ impl UpdateWithSession<'_> {
 
         let mut instruction_data = [0u8; 256 + DISCRIMATOR_LENGTH];
 
-        instruction_data[0..DISCRIMATOR_LENGTH].copy_from_slice(Self::DISCRIMINATOR.as_slice());
-        instruction_data[DISCRIMATOR_LENGTH..DISCRIMATOR_LENGTH + self.instruction_data.len()]
-            .copy_from_slice(self.instruction_data);
-
         let instruction = Instruction {
             program_id: self.component_program,
             accounts: &account_metas,
-            data: &instruction_data[..DISCRIMATOR_LENGTH + self.instruction_data.len()],
+            data: copy_instruction_data(
+                &mut instruction_data,
+                &Self::DISCRIMINATOR,
+                self.instruction_data,
+            )?,
         };
 
         invoke_signed(
@@ -1,4 +1,5 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 pub mod component;
 pub mod system;
+mod utils;
@@ -0,0 +1,97 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// Context the world passes to a system ahead of its instruction data when
+/// invoking it through `Execute` with a context. Serialized as
+/// `[version u8][world u64][slot u64][unix_timestamp i64][caller]
+/// [session u8][entities u8][entity pubkeys]`, all integers little-endian.
+pub struct ExecutionContext<'a> {
+    pub world: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    /// Authority that applied the system.
+    pub caller: &'a Pubkey,
+    /// Whether the caller signed through a session token.
+    pub session: bool,
+    /// Entity of each component, in the order the components are passed.
+    pub entities: &'a [&'a AccountInfo],
+}
+
+impl ExecutionContext<'_> {
+    pub const VERSION: u8 = 1;
+
+    /// Serialized size of the context.
+    pub fn size(&self) -> usize {
+        1 + 8 + 8 + 8 + 32 + 1 + 1 + self.entities.len() * 32
+    }
+
+    /// Writes the serialized context at the start of `buffer`, returning its length.
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, ProgramError> {
+        let entities =
+            u8::try_from(self.entities.len()).map_err(|_| ProgramError::InvalidArgument)?;
+
+        let buffer = buffer
+            .get_mut(..self.size())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        buffer[0] = Self::VERSION;
+        buffer[1..9].copy_from_slice(&self.world.to_le_bytes());
+        buffer[9..17].copy_from_slice(&self.slot.to_le_bytes());
+        buffer[17..25].copy_from_slice(&self.unix_timestamp.to_le_bytes());
+        buffer[25..57].copy_from_slice(self.caller);
+        buffer[57] = self.session as u8;
+        buffer[58] = entities;
+
+        let mut len = 59;
+        for entity in self.entities {
+            buffer[len..len + 32].copy_from_slice(entity.key());
+            len += 32;
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(caller: &Pubkey) -> ExecutionContext<'_> {
+        ExecutionContext {
+            world: 3,
+            slot: 4,
+            unix_timestamp: -5,
+            caller,
+            session: true,
+            entities: &[],
+        }
+    }
+
+    #[test]
+    fn writes_context() {
+        let caller = [7; 32];
+        let context = context(&caller);
+        let mut buffer = [0xff; 64];
+
+        assert_eq!(context.write(&mut buffer).unwrap(), context.size());
+        assert_eq!(buffer[0], ExecutionContext::VERSION);
+        assert_eq!(&buffer[1..9], &3u64.to_le_bytes());
+        assert_eq!(&buffer[9..17], &4u64.to_le_bytes());
+        assert_eq!(&buffer[17..25], &(-5i64).to_le_bytes());
+        assert_eq!(&buffer[25..57], &caller);
+        assert_eq!(buffer[57], 1);
+        assert_eq!(buffer[58], 0);
+        assert_eq!(buffer[59], 0xff);
+    }
+
+    #[test]
+    fn rejects_short_buffer() {
+        let caller = [7; 32];
+        let context = context(&caller);
+        let mut buffer = [0; 58];
+
+        assert_eq!(
+            context.write(&mut buffer),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -4,25 +4,70 @@ use pinocchio::{
     account_info::AccountInfo,
     cpi::{slice_invoke_signed, MAX_CPI_ACCOUNTS},
     instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
     pubkey::Pubkey,
     ProgramResult,
 };
 
+use super::ExecutionContext;
+
+/// CPI to the `execute` instruction of a system. The read-only components and
+/// the context were added in 0.2.0, which broke struct literals; prefer
+/// `Execute::new` and the `with_*` methods, which keep compiling as optional
+/// inputs are added.
 pub struct Execute<'a> {
     pub authority: &'a AccountInfo,
 
     pub components: &'a [&'a AccountInfo],
 
+    /// Components the system only reads, passed after `components` as read-only.
+    pub readonly_components: &'a [&'a AccountInfo],
+
+    /// Extra accounts, forwarded with the writable and signer flags they have
+    /// in the calling instruction.
     pub remaining_accounts: &'a [&'a AccountInfo],
 
     pub system: &'a Pubkey,
 
+    /// Context sent ahead of `instruction_data`, switching to `execute_with_context`.
+    pub context: Option<&'a ExecutionContext<'a>>,
+
     pub instruction_data: &'a [u8],
 }
 
-impl Execute<'_> {
+impl<'a> Execute<'a> {
     pub const DISCRIMINATOR: [u8; 8] = [75, 206, 62, 210, 52, 215, 104, 109];
 
+    pub const WITH_CONTEXT_DISCRIMINATOR: [u8; 8] = [216, 72, 177, 255, 198, 107, 59, 105];
+
+    pub fn new(
+        authority: &'a AccountInfo,
+        components: &'a [&'a AccountInfo],
+        remaining_accounts: &'a [&'a AccountInfo],
+        system: &'a Pubkey,
+        instruction_data: &'a [u8],
+    ) -> Self {
+        Self {
+            authority,
+            components,
+            readonly_components: &[],
+            remaining_accounts,
+            system,
+            context: None,
+            instruction_data,
+        }
+    }
+
+    pub fn with_readonly_components(mut self, readonly_components: &'a [&'a AccountInfo]) -> Self {
+        self.readonly_components = readonly_components;
+        self
+    }
+
+    pub fn with_context(mut self, context: &'a ExecutionContext<'a>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
     #[inline(always)]
     pub fn invoke(&self) -> ProgramResult {
         self.invoke_signed(&[])
@@ -35,6 +80,14 @@ impl Execute<'_> {
         let mut maybe_account_infos = [UNINIT; MAX_CPI_ACCOUNTS];
         let mut maybe_account_metas = [UNINIT_METAS; MAX_CPI_ACCOUNTS];
 
+        if 1 + self.components.len()
+            + self.readonly_components.len()
+            + self.remaining_accounts.len()
+            > MAX_CPI_ACCOUNTS
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
         let mut len = 0;
 
         maybe_account_infos[len].write(self.authority);
@@ -49,11 +102,20 @@ impl Execute<'_> {
             }
         }
 
+        for component in self.readonly_components {
+            maybe_account_infos[len].write(component);
+            maybe_account_metas[len].write(AccountMeta::readonly(component.key()));
+            len += 1;
+        }
+
         if !self.remaining_accounts.is_empty() {
             for i in 0..self.remaining_accounts.len() {
                 maybe_account_infos[len].write(self.remaining_accounts[i]);
-                maybe_account_metas[len]
-                    .write(AccountMeta::readonly(self.remaining_accounts[i].key()));
+                maybe_account_metas[len].write(AccountMeta::new(
+                    self.remaining_accounts[i].key(),
+                    self.remaining_accounts[i].is_writable(),
+                    self.remaining_accounts[i].is_signer(),
+                ));
                 len += 1;
             }
         }
@@ -68,14 +130,33 @@ impl Execute<'_> {
 
         const DISCRIMATOR_LENGTH: usize = 8;
 
-        instruction_data[0..DISCRIMATOR_LENGTH].copy_from_slice(Self::DISCRIMINATOR.as_slice());
-        instruction_data[DISCRIMATOR_LENGTH..DISCRIMATOR_LENGTH + self.instruction_data.len()]
+        if DISCRIMATOR_LENGTH
+            + self.context.map_or(0, |context| context.size())
+            + self.instruction_data.len()
+            > instruction_data.len()
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut offset = DISCRIMATOR_LENGTH;
+
+        match self.context {
+            Some(context) => {
+                instruction_data[0..DISCRIMATOR_LENGTH]
+                    .copy_from_slice(Self::WITH_CONTEXT_DISCRIMINATOR.as_slice());
+                offset += context.write(&mut instruction_data[DISCRIMATOR_LENGTH..])?;
+            }
+            None => instruction_data[0..DISCRIMATOR_LENGTH]
+                .copy_from_slice(Self::DISCRIMINATOR.as_slice()),
+        }
+
+        instruction_data[offset..offset + self.instruction_data.len()]
             .copy_from_slice(self.instruction_data);
 
         let instruction = Instruction {
             program_id: self.system,
             accounts: account_metas,
-            data: &instruction_data[..DISCRIMATOR_LENGTH + self.instruction_data.len()],
+            data: &instruction_data[..offset + self.instruction_data.len()],
         };
 
         slice_invoke_signed(&instruction, account_infos, signers)
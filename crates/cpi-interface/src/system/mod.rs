@@ -1,2 +1,5 @@
+mod context;
+pub use context::*;
+
 mod execute;
 pub use execute::*;
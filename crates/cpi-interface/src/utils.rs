@@ -0,0 +1,50 @@
+use pinocchio::program_error::ProgramError;
+
+/// Writes `discriminator` followed by `data` into `buffer`, returning the
+/// written instruction data, or `InvalidInstructionData` if it does not fit.
+pub(crate) fn copy_instruction_data<'a>(
+    buffer: &'a mut [u8],
+    discriminator: &[u8],
+    data: &[u8],
+) -> Result<&'a [u8], ProgramError> {
+    let len = discriminator.len() + data.len();
+
+    let instruction_data = buffer
+        .get_mut(..len)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let (prefix, rest) = instruction_data.split_at_mut(discriminator.len());
+    prefix.copy_from_slice(discriminator);
+    rest.copy_from_slice(data);
+
+    Ok(instruction_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_discriminator_and_data() {
+        let mut buffer = [0u8; 6];
+
+        assert_eq!(
+            copy_instruction_data(&mut buffer, &[1, 2], &[3, 4, 5]).unwrap(),
+            &[1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            copy_instruction_data(&mut buffer, &[1, 2], &[3; 4]).unwrap(),
+            &[1, 2, 3, 3, 3, 3]
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_data() {
+        let mut buffer = [0u8; 6];
+
+        assert_eq!(
+            copy_instruction_data(&mut buffer, &[1, 2], &[3; 5]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{compute_root, hash_pair, MerkleError, Node, EMPTY_NODE};
+
+/// Path written by one tree update: `path[h]` is the new node at height `h`
+/// on the way from the leaf (`path[0]`) to `root`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ChangeLog<const DEPTH: usize> {
+    pub root: Node,
+    pub path: [Node; DEPTH],
+    pub index: u32,
+    _padding: u32,
+}
+
+/// Proof of the rightmost appended leaf, `index` being the number of leaves.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Path<const DEPTH: usize> {
+    pub proof: [Node; DEPTH],
+    pub leaf: Node,
+    pub index: u32,
+    _padding: u32,
+}
+
+/// Merkle tree that keeps the paths of its last `BUFFER` updates so proofs
+/// made against any of the matching roots can be fast-forwarded to the current
+/// root, letting several updates of the same slot go through concurrently.
+///
+/// The struct is `repr(C)` and plain data so it can be laid over account data.
+#[repr(C)]
+pub struct ConcurrentMerkleTree<const DEPTH: usize, const BUFFER: usize> {
+    pub sequence_number: u64,
+    pub active_index: u64,
+    pub buffer_size: u64,
+    pub rightmost_proof: Path<DEPTH>,
+    pub change_logs: [ChangeLog<DEPTH>; BUFFER],
+}
+
+impl<const DEPTH: usize, const BUFFER: usize> ConcurrentMerkleTree<DEPTH, BUFFER> {
+    pub const CAPACITY: u64 = 1 << DEPTH;
+
+    pub fn new() -> Self {
+        let mut tree = Self {
+            sequence_number: 0,
+            active_index: 0,
+            buffer_size: 0,
+            rightmost_proof: Path {
+                proof: [EMPTY_NODE; DEPTH],
+                leaf: EMPTY_NODE,
+                index: 0,
+                _padding: 0,
+            },
+            change_logs: [ChangeLog {
+                root: EMPTY_NODE,
+                path: [EMPTY_NODE; DEPTH],
+                index: 0,
+                _padding: 0,
+            }; BUFFER],
+        };
+        tree.initialize();
+        tree
+    }
+
+    /// Resets the tree to an empty one.
+    pub fn initialize(&mut self) {
+        let mut empty = EMPTY_NODE;
+        for height in 0..DEPTH {
+            self.rightmost_proof.proof[height] = empty;
+            self.change_logs[0].path[height] = empty;
+            empty = hash_pair(&empty, &empty);
+        }
+
+        self.rightmost_proof.leaf = EMPTY_NODE;
+        self.rightmost_proof.index = 0;
+        self.change_logs[0].root = empty;
+        self.change_logs[0].index = 0;
+        self.sequence_number = 0;
+        self.active_index = 0;
+        self.buffer_size = 1;
+    }
+
+    pub fn root(&self) -> Node {
+        self.change_logs[self.active_index as usize].root
+    }
+
+    /// Number of appended leaves.
+    pub fn len(&self) -> u32 {
+        self.rightmost_proof.index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `leaf` after the rightmost leaf, returning its index.
+    pub fn append(&mut self, leaf: Node) -> Result<u32, MerkleError> {
+        let index = self.rightmost_proof.index;
+
+        if index as u64 >= Self::CAPACITY {
+            return Err(MerkleError::TreeFull);
+        }
+
+        // The left siblings of the new leaf are nodes of the previous rightmost
+        // path or its proof; its right siblings are all empty.
+        let previous = index.wrapping_sub(1);
+        let mut proof = [EMPTY_NODE; DEPTH];
+        let mut node = self.rightmost_proof.leaf;
+        let mut empty = EMPTY_NODE;
+
+        for (height, sibling) in proof.iter_mut().enumerate() {
+            *sibling = match (index >> height) & 1 {
+                0 => empty,
+                _ if previous >> height == (index >> height) - 1 => node,
+                _ => self.rightmost_proof.proof[height],
+            };
+
+            if index != 0 {
+                node = match (previous >> height) & 1 {
+                    0 => hash_pair(&node, &self.rightmost_proof.proof[height]),
+                    _ => hash_pair(&self.rightmost_proof.proof[height], &node),
+                };
+            }
+            empty = hash_pair(&empty, &empty);
+        }
+
+        self.apply(index, &leaf, &proof);
+
+        self.rightmost_proof.proof = proof;
+        self.rightmost_proof.leaf = leaf;
+        self.rightmost_proof.index = index + 1;
+
+        Ok(index)
+    }
+
+    /// Replaces `previous_leaf` at `index` by `new_leaf`, given a proof made
+    /// against `root`, which may be any root still in the changelog buffer.
+    pub fn set_leaf(
+        &mut self,
+        root: &Node,
+        previous_leaf: &Node,
+        new_leaf: &Node,
+        proof: &[Node],
+        index: u32,
+    ) -> Result<(), MerkleError> {
+        if proof.len() != DEPTH {
+            return Err(MerkleError::InvalidProof);
+        }
+
+        if index >= self.rightmost_proof.index {
+            return Err(MerkleError::LeafIndexOutOfBounds);
+        }
+
+        let age = (0..self.buffer_size)
+            .find(|age| &self.change_logs[self.buffer_index(*age)].root == root)
+            .ok_or(MerkleError::RootNotFound)?;
+
+        let mut updated_proof = [EMPTY_NODE; DEPTH];
+        updated_proof.copy_from_slice(proof);
+
+        for age in (0..age).rev() {
+            let change_log = &self.change_logs[self.buffer_index(age)];
+
+            if change_log.index == index {
+                return Err(MerkleError::LeafModified);
+            }
+
+            let height = critical_height(change_log.index, index);
+            updated_proof[height] = change_log.path[height];
+        }
+
+        if compute_root(previous_leaf, &updated_proof, index) != self.root() {
+            return Err(MerkleError::InvalidProof);
+        }
+
+        self.apply(index, new_leaf, &updated_proof);
+
+        let rightmost = self.rightmost_proof.index - 1;
+        let change_log = &self.change_logs[self.active_index as usize];
+
+        if rightmost == index {
+            self.rightmost_proof.leaf = *new_leaf;
+        } else {
+            let height = critical_height(rightmost, index);
+            self.rightmost_proof.proof[height] = change_log.path[height];
+        }
+
+        Ok(())
+    }
+
+    /// Position in the changelog buffer of the update `age` updates ago.
+    fn buffer_index(&self, age: u64) -> usize {
+        ((self.active_index + BUFFER as u64 - age) % BUFFER as u64) as usize
+    }
+
+    /// Writes `leaf` at `index` and records the new path as the latest changelog.
+    fn apply(&mut self, index: u32, leaf: &Node, proof: &[Node; DEPTH]) {
+        self.active_index = (self.active_index + 1) % BUFFER as u64;
+        self.sequence_number += 1;
+        self.buffer_size = (self.buffer_size + 1).min(BUFFER as u64);
+
+        let change_log = &mut self.change_logs[self.active_index as usize];
+
+        let mut node = *leaf;
+        for (height, sibling) in proof.iter().enumerate() {
+            change_log.path[height] = node;
+            node = match (index >> height) & 1 {
+                0 => hash_pair(&node, sibling),
+                _ => hash_pair(sibling, &node),
+            };
+        }
+
+        change_log.root = node;
+        change_log.index = index;
+    }
+}
+
+impl<const DEPTH: usize, const BUFFER: usize> Default for ConcurrentMerkleTree<DEPTH, BUFFER> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Height at which the paths of two different leaves meet as siblings.
+fn critical_height(a: u32, b: u32) -> usize {
+    (u32::BITS - 1 - (a ^ b).leading_zeros()) as usize
+}
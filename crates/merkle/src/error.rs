@@ -0,0 +1,13 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleError {
+    /// Every leaf of the tree has been appended.
+    TreeFull,
+    /// The leaf index is past the rightmost appended leaf.
+    LeafIndexOutOfBounds,
+    /// The root the proof was made against is no longer in the changelog buffer.
+    RootNotFound,
+    /// The leaf was changed after the root the proof was made against.
+    LeafModified,
+    /// The proof does not lead from the leaf to the root.
+    InvalidProof,
+}
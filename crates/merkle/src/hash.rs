@@ -0,0 +1,55 @@
+pub type Node = [u8; 32];
+
+/// Leaf value of slots that were never appended.
+pub const EMPTY_NODE: Node = [0; 32];
+
+pub fn hashv(vals: &[&[u8]]) -> Node {
+    let mut hash = [0u8; 32];
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_sha256(
+            vals as *const _ as *const u8,
+            vals.len() as u64,
+            hash.as_mut_ptr(),
+        );
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::Digest;
+
+        let mut hasher = sha2::Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hash.copy_from_slice(&hasher.finalize());
+    }
+
+    hash
+}
+
+pub fn hash_pair(left: &Node, right: &Node) -> Node {
+    hashv(&[left, right])
+}
+
+/// Root of an empty subtree of the given height.
+pub fn empty_node(height: usize) -> Node {
+    let mut node = EMPTY_NODE;
+    for _ in 0..height {
+        node = hash_pair(&node, &node);
+    }
+    node
+}
+
+/// Root reached by hashing `leaf` at `index` up through `proof`.
+pub fn compute_root(leaf: &Node, proof: &[Node], index: u32) -> Node {
+    let mut node = *leaf;
+    for (height, sibling) in proof.iter().enumerate() {
+        node = match (index >> height) & 1 {
+            0 => hash_pair(&node, sibling),
+            _ => hash_pair(sibling, &node),
+        };
+    }
+    node
+}
@@ -0,0 +1,79 @@
+extern crate alloc;
+
+use crate::{empty_node, hash_pair, Node};
+use alloc::vec::Vec;
+
+/// Full Merkle tree kept by clients and indexers to produce the proofs the
+/// on-chain `ConcurrentMerkleTree` verifies.
+pub struct MerkleTree {
+    /// Non-empty nodes of each height, leaves first.
+    layers: Vec<Vec<Node>>,
+    empty: Vec<Node>,
+}
+
+impl MerkleTree {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            layers: (0..=depth).map(|_| Vec::new()).collect(),
+            empty: (0..=depth).map(empty_node).collect(),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn leaves(&self) -> &[Node] {
+        &self.layers[0]
+    }
+
+    pub fn root(&self) -> Node {
+        self.node(self.depth(), 0)
+    }
+
+    pub fn append(&mut self, leaf: Node) -> u32 {
+        let index = self.layers[0].len() as u32;
+        self.layers[0].push(leaf);
+        self.update_path(index);
+        index
+    }
+
+    /// Replaces the leaf at `index`, which must already be appended.
+    pub fn set(&mut self, index: u32, leaf: Node) {
+        self.layers[0][index as usize] = leaf;
+        self.update_path(index);
+    }
+
+    /// Sibling nodes from the leaf at `index` up to the root.
+    pub fn proof(&self, index: u32) -> Vec<Node> {
+        (0..self.depth())
+            .map(|height| self.node(height, ((index >> height) ^ 1) as usize))
+            .collect()
+    }
+
+    fn node(&self, height: usize, index: usize) -> Node {
+        self.layers[height]
+            .get(index)
+            .copied()
+            .unwrap_or(self.empty[height])
+    }
+
+    fn update_path(&mut self, index: u32) {
+        let mut index = index as usize;
+
+        for height in 0..self.depth() {
+            let parent = hash_pair(
+                &self.node(height, index & !1),
+                &self.node(height, index | 1),
+            );
+
+            index >>= 1;
+
+            let layer = &mut self.layers[height + 1];
+            match layer.get_mut(index) {
+                Some(node) => *node = parent,
+                None => layer.push(parent),
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+#![no_std]
+#![allow(unexpected_cfgs)]
+
+mod concurrent;
+pub use concurrent::*;
+
+mod error;
+pub use error::*;
+
+mod hash;
+pub use hash::*;
+
+#[cfg(not(target_os = "solana"))]
+mod host;
+#[cfg(not(target_os = "solana"))]
+pub use host::*;
@@ -0,0 +1,161 @@
+use hermes_merkle::{empty_node, hashv, ConcurrentMerkleTree, MerkleError, MerkleTree, Node};
+
+const DEPTH: usize = 5;
+const BUFFER: usize = 4;
+
+type Tree = ConcurrentMerkleTree<DEPTH, BUFFER>;
+
+fn leaf(i: u64) -> Node {
+    hashv(&[&i.to_le_bytes()])
+}
+
+fn filled(leaves: u64) -> (Tree, MerkleTree) {
+    let mut tree = Tree::new();
+    let mut host = MerkleTree::new(DEPTH);
+
+    for i in 0..leaves {
+        assert_eq!(tree.append(leaf(i)), Ok(host.append(leaf(i))));
+    }
+
+    (tree, host)
+}
+
+#[test]
+fn empty_tree_has_empty_root() {
+    let tree = Tree::new();
+
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), empty_node(DEPTH));
+    assert_eq!(tree.root(), MerkleTree::new(DEPTH).root());
+}
+
+#[test]
+fn append_matches_host_tree() {
+    let mut tree = Tree::new();
+    let mut host = MerkleTree::new(DEPTH);
+
+    for i in 0..20 {
+        tree.append(leaf(i)).unwrap();
+        host.append(leaf(i));
+        assert_eq!(tree.root(), host.root());
+    }
+
+    assert_eq!(tree.len(), 20);
+}
+
+#[test]
+fn append_fails_when_full() {
+    let (mut tree, _) = filled(1 << DEPTH);
+
+    assert_eq!(tree.append(leaf(0)), Err(MerkleError::TreeFull));
+}
+
+#[test]
+fn set_leaf_with_current_proof() {
+    let (mut tree, mut host) = filled(9);
+
+    for index in [0, 4, 8] {
+        let root = tree.root();
+        let proof = host.proof(index);
+
+        tree.set_leaf(&root, &leaf(index as u64), &leaf(100), &proof, index)
+            .unwrap();
+        host.set(index, leaf(100));
+
+        assert_eq!(tree.root(), host.root());
+    }
+}
+
+#[test]
+fn set_leaf_fast_forwards_stale_proofs() {
+    let (mut tree, mut host) = filled(12);
+
+    let root = host.root();
+    let proofs: Vec<_> = [1, 2, 6, 11].iter().map(|i| (*i, host.proof(*i))).collect();
+
+    for (index, proof) in proofs {
+        tree.set_leaf(
+            &root,
+            &leaf(index as u64),
+            &leaf(200 + index as u64),
+            &proof,
+            index,
+        )
+        .unwrap();
+        host.set(index, leaf(200 + index as u64));
+
+        assert_eq!(tree.root(), host.root());
+    }
+}
+
+#[test]
+fn appends_after_updates_match_host_tree() {
+    let (mut tree, mut host) = filled(6);
+
+    for index in [5, 2, 0] {
+        let proof = host.proof(index);
+        tree.set_leaf(&tree.root(), &leaf(index as u64), &leaf(300), &proof, index)
+            .unwrap();
+        host.set(index, leaf(300));
+    }
+
+    for i in 6..14 {
+        tree.append(leaf(i)).unwrap();
+        host.append(leaf(i));
+        assert_eq!(tree.root(), host.root());
+    }
+}
+
+#[test]
+fn set_leaf_rejects_roots_out_of_the_buffer() {
+    let (mut tree, mut host) = filled(2);
+
+    let root = host.root();
+    let proof = host.proof(0);
+
+    for i in 2..2 + BUFFER as u64 {
+        tree.append(leaf(i)).unwrap();
+        host.append(leaf(i));
+    }
+
+    assert_eq!(
+        tree.set_leaf(&root, &leaf(0), &leaf(400), &proof, 0),
+        Err(MerkleError::RootNotFound)
+    );
+}
+
+#[test]
+fn set_leaf_rejects_modified_leaves() {
+    let (mut tree, host) = filled(4);
+
+    let root = host.root();
+    let proof = host.proof(3);
+
+    tree.set_leaf(&root, &leaf(3), &leaf(500), &proof, 3)
+        .unwrap();
+
+    assert_eq!(
+        tree.set_leaf(&root, &leaf(3), &leaf(501), &proof, 3),
+        Err(MerkleError::LeafModified)
+    );
+}
+
+#[test]
+fn set_leaf_rejects_invalid_proofs() {
+    let (mut tree, host) = filled(4);
+
+    let root = host.root();
+
+    assert_eq!(
+        tree.set_leaf(&root, &leaf(1), &leaf(600), &host.proof(2), 2),
+        Err(MerkleError::InvalidProof)
+    );
+    assert_eq!(
+        tree.set_leaf(&root, &leaf(1), &leaf(600), &host.proof(1)[1..], 1),
+        Err(MerkleError::InvalidProof)
+    );
+    assert_eq!(
+        tree.set_leaf(&root, &leaf(4), &leaf(600), &host.proof(4), 4),
+        Err(MerkleError::LeafIndexOutOfBounds)
+    );
+}
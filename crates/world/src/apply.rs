@@ -0,0 +1,720 @@
+use crate::{
+    error::WorldError,
+    instructions::create_entity,
+    state::{
+        entity::Entity,
+        output_buffer::OutputBuffer,
+        world::{WorldMut, WorldRef},
+    },
+    system_output::{ComponentOutput, SystemCommand, SystemOutput},
+    utils::account_refs,
+};
+use core::mem::MaybeUninit;
+use hermes_cpi_interface::system::ExecutionContext;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::MAX_CPI_ACCOUNTS,
+    log::sol_log_data,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+/// The authority signs through a session token, passed after the world.
+pub const APPLY_FLAG_SESSION: u8 = 1 << 0;
+
+/// Components come with their entity and the system is sent an `ExecutionContext`.
+pub const APPLY_FLAG_CONTEXT: u8 = 1 << 1;
+
+/// The system's output buffer and its `OutputBuffer` record follow the session
+/// token. Both are forwarded to the system after the extra accounts and
+/// components may be written back from the buffer once per stamp.
+pub const APPLY_FLAG_BUFFER: u8 = 1 << 2;
+
+/// `[flags u8][components u8][read-only components u8][extra accounts u8]`
+/// header describing the accounts of an apply.
+pub struct ApplyHeader {
+    pub flags: u8,
+    pub components: u8,
+    pub readonly_components: u8,
+    pub extras: u8,
+}
+
+impl ApplyHeader {
+    pub const LEN: usize = 4;
+
+    pub fn parse(data: &[u8]) -> Result<(Self, &[u8]), ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (header, rest) = data.split_at(Self::LEN);
+
+        let header = Self {
+            flags: header[0],
+            components: header[1],
+            readonly_components: header[2],
+            extras: header[3],
+        };
+
+        if header.flags & !(APPLY_FLAG_SESSION | APPLY_FLAG_CONTEXT | APPLY_FLAG_BUFFER) != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok((header, rest))
+    }
+}
+
+/// Accounts of an apply: the authority, the instruction sysvar, the world, the
+/// session token and the output buffer with its record if flagged, then the `(component_program, component)` pairs,
+/// or `(component_program, component, entity)` triples with a context, the
+/// read-only ones and the extra accounts.
+pub struct ApplyContext<'a> {
+    pub authority: &'a AccountInfo,
+    pub instruction_sysvar_account: &'a AccountInfo,
+    pub world: &'a AccountInfo,
+    pub session_token: Option<&'a AccountInfo>,
+    pub output_buffer: Option<&'a AccountInfo>,
+    pub output_buffer_record: Option<&'a AccountInfo>,
+    pub flags: u8,
+    pub components: &'a [AccountInfo],
+    /// Components the system reads but doesn't write back.
+    pub readonly_components: &'a [AccountInfo],
+    pub extras: &'a [AccountInfo],
+}
+
+/// Accounts of one component of an apply.
+pub struct ApplyComponent<'a> {
+    pub program: &'a AccountInfo,
+    pub component: &'a AccountInfo,
+    pub entity: Option<&'a AccountInfo>,
+}
+
+impl<'a> ApplyContext<'a> {
+    /// Parses the accounts described by `header`, returning the ones after them.
+    pub fn parse(
+        accounts: &'a [AccountInfo],
+        header: &ApplyHeader,
+    ) -> Result<(Self, &'a [AccountInfo]), ProgramError> {
+        let [authority, instruction_sysvar_account, world, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (session_token, remaining) = match header.flags & APPLY_FLAG_SESSION {
+            0 => (None, remaining),
+            _ => remaining
+                .split_first()
+                .map(|(session_token, rest)| (Some(session_token), rest))
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+        };
+
+        let (output_buffer, output_buffer_record, remaining) =
+            match (header.flags & APPLY_FLAG_BUFFER, remaining) {
+                (0, _) => (None, None, remaining),
+                (_, [output_buffer, output_buffer_record, rest @ ..]) => {
+                    (Some(output_buffer), Some(output_buffer_record), rest)
+                }
+                _ => return Err(ProgramError::NotEnoughAccountKeys),
+            };
+
+        let ctx = Self {
+            authority,
+            instruction_sysvar_account,
+            world,
+            session_token,
+            output_buffer,
+            output_buffer_record,
+            flags: header.flags,
+            components: &[],
+            readonly_components: &[],
+            extras: &[],
+        };
+
+        ctx.next_group(remaining, header)
+    }
+
+    /// Context sharing these fixed accounts for the group of component and
+    /// extra accounts described by `header`, returning the accounts after it.
+    pub fn next_group(
+        &self,
+        accounts: &'a [AccountInfo],
+        header: &ApplyHeader,
+    ) -> Result<(Self, &'a [AccountInfo]), ProgramError> {
+        if header.flags != self.flags {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let stride = stride(header.flags);
+        let components = header.components as usize * stride;
+        let readonly_components = header.readonly_components as usize * stride;
+
+        if accounts.len() < components + readonly_components + header.extras as usize {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let (components, accounts) = accounts.split_at(components);
+        let (readonly_components, accounts) = accounts.split_at(readonly_components);
+        let (extras, accounts) = accounts.split_at(header.extras as usize);
+
+        Ok((
+            Self {
+                authority: self.authority,
+                instruction_sysvar_account: self.instruction_sysvar_account,
+                world: self.world,
+                session_token: self.session_token,
+                output_buffer: self.output_buffer,
+                output_buffer_record: self.output_buffer_record,
+                flags: self.flags,
+                components,
+                readonly_components,
+                extras,
+            },
+            accounts,
+        ))
+    }
+
+    /// Context of `apply_system` and `apply_system_session` with
+    /// `APPLY_FLAG_CONTEXT`: `(component_program, component, entity)` triples,
+    /// then the extra accounts after a `crate::ID` separator if any.
+    pub fn parse_legacy(
+        authority: &'a AccountInfo,
+        instruction_sysvar_account: &'a AccountInfo,
+        world: &'a AccountInfo,
+        session_token: Option<&'a AccountInfo>,
+        accounts: &'a [AccountInfo],
+    ) -> Result<Self, ProgramError> {
+        let stride = stride(APPLY_FLAG_CONTEXT);
+
+        let (components, extras) = match accounts
+            .chunks(stride)
+            .position(|accounts| accounts[0].key() == &crate::ID)
+        {
+            Some(separator) => {
+                let (components, rest) = accounts.split_at(separator * stride);
+                (components, &rest[1..])
+            }
+            None if accounts.len().is_multiple_of(stride) => (accounts, [].as_slice()),
+            None => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        Ok(Self {
+            authority,
+            instruction_sysvar_account,
+            world,
+            session_token,
+            output_buffer: None,
+            output_buffer_record: None,
+            flags: APPLY_FLAG_CONTEXT,
+            components,
+            readonly_components: &[],
+            extras,
+        })
+    }
+
+    pub fn has_context(&self) -> bool {
+        self.flags & APPLY_FLAG_CONTEXT != 0
+    }
+
+    /// Components written back from the system output.
+    pub fn writable(&self) -> impl Iterator<Item = ApplyComponent<'a>> {
+        components(self.components, stride(self.flags))
+    }
+
+    pub fn readonly(&self) -> impl Iterator<Item = ApplyComponent<'a>> {
+        components(self.readonly_components, stride(self.flags))
+    }
+
+    pub fn writable_len(&self) -> usize {
+        self.components.len() / stride(self.flags)
+    }
+
+    /// Checks the authority and that the world approves every one of `systems`,
+    /// returning the world id. The world data is not borrowed past this call,
+    /// leaving commands free to write it.
+    pub fn authorize(&self, systems: &[AccountInfo]) -> Result<u64, ProgramError> {
+        if !self.authority.is_signer() && self.authority.key() != &crate::ID {
+            return Err(WorldError::InvalidAuthority.into());
+        }
+
+        let world = WorldRef::from_account_info(self.world)?;
+
+        for system in systems {
+            if !world.is_system_approved(system.key())? {
+                return Err(WorldError::SystemNotApproved.into());
+            }
+        }
+
+        Ok(world.metadata.id)
+    }
+
+    /// Checks that the entities passed with the components belong to `world`
+    /// and have these components.
+    pub fn assert_entities(&self, world: u64) -> ProgramResult {
+        for component in self.writable().chain(self.readonly()) {
+            component.assert_entity(world)?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs `system` over the components, writes its output back and runs the
+    /// commands it returned. The system approval is left to the caller.
+    pub fn execute(&self, world: u64, system: &AccountInfo, args: &[u8]) -> ProgramResult {
+        const UNINIT_INFO: MaybeUninit<&AccountInfo> = MaybeUninit::uninit();
+
+        let mut component_refs = [UNINIT_INFO; MAX_CPI_ACCOUNTS];
+        let mut readonly_refs = [UNINIT_INFO; MAX_CPI_ACCOUNTS];
+        let mut extra_refs = [UNINIT_INFO; MAX_CPI_ACCOUNTS];
+        let mut entity_refs = [UNINIT_INFO; MAX_CPI_ACCOUNTS];
+
+        let context = match self.has_context() {
+            true => {
+                let clock = Clock::get()?;
+
+                Some(ExecutionContext {
+                    world,
+                    slot: clock.slot,
+                    unix_timestamp: clock.unix_timestamp,
+                    caller: self.authority.key(),
+                    session: self.session_token.is_some(),
+                    entities: account_refs(
+                        self.writable()
+                            .chain(self.readonly())
+                            .filter_map(|component| component.entity),
+                        &mut entity_refs,
+                    )?,
+                })
+            }
+            false => None,
+        };
+
+        hermes_cpi_interface::system::Execute {
+            authority: self.authority,
+            components: account_refs(
+                self.writable().map(|component| component.component),
+                &mut component_refs,
+            )?,
+            readonly_components: account_refs(
+                self.readonly().map(|component| component.component),
+                &mut readonly_refs,
+            )?,
+            remaining_accounts: account_refs(
+                self.extras
+                    .iter()
+                    .chain(self.output_buffer)
+                    .chain(self.output_buffer_record),
+                &mut extra_refs,
+            )?,
+            instruction_data: args,
+            system: system.key(),
+            context: context.as_ref(),
+        }
+        .invoke()?;
+
+        let output = SystemOutput::get_with_commands(system.key(), self.writable_len())?;
+
+        let output_buffer = match output
+            .components()
+            .any(|output| matches!(output, ComponentOutput::Buffer { .. }))
+        {
+            true => Some(self.consume_output_buffer(system)?),
+            false => None,
+        };
+
+        for (component, output) in self.writable().zip(output.components()) {
+            output.write_back(
+                self.authority,
+                component.program,
+                component.component,
+                self.instruction_sysvar_account,
+                self.session_token,
+                output_buffer,
+            )?;
+        }
+
+        for command in output.commands() {
+            self.run_command(world, system, command?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the output buffer is the one `system` writes to for the
+    /// authority and carries the current stamp, which is then retired.
+    fn consume_output_buffer(&self, system: &AccountInfo) -> Result<&'a AccountInfo, ProgramError> {
+        let (Some(output_buffer), Some(record_acct)) =
+            (self.output_buffer, self.output_buffer_record)
+        else {
+            return Err(WorldError::InvalidSystemOutput.into());
+        };
+
+        let record = OutputBuffer::from_account_info_mut(record_acct)?;
+
+        if &record.system != system.key() || &record.authority != self.authority.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        record.consume(output_buffer)?;
+
+        Ok(output_buffer)
+    }
+
+    fn extra(&self, index: u8) -> Result<&'a AccountInfo, ProgramError> {
+        self.extras
+            .get(index as usize)
+            .ok_or(WorldError::InvalidSystemOutput.into())
+    }
+
+    /// Checks that the authority may edit `entity` as its owner, like the
+    /// instructions editing an entity on behalf of `system` do.
+    fn assert_entity_authority(&self, entity: &Entity, system: &AccountInfo) -> ProgramResult {
+        let world = WorldRef::from_account_info(self.world)?;
+        entity.assert_authority(self.authority, &world, Some(system))
+    }
+
+    /// Runs a command returned by `system`, already approved by `world`, with
+    /// the same checks as the matching instruction. Components can only be
+    /// initialized or destroyed on entities of `world` the authority may edit.
+    fn run_command(
+        &self,
+        world: u64,
+        system: &AccountInfo,
+        command: SystemCommand,
+    ) -> ProgramResult {
+        match command {
+            SystemCommand::SpawnEntity {
+                payer,
+                entity,
+                free_list,
+                expiry_slot,
+            } => {
+                let free_list = free_list.map(|index| self.extra(index)).transpose()?;
+                let mut world = WorldMut::from_account_info(self.world)?;
+
+                create_entity(
+                    self.extra(payer)?,
+                    self.extra(entity)?,
+                    &mut world,
+                    None,
+                    expiry_slot,
+                    free_list,
+                )?;
+            }
+            SystemCommand::InitializeComponent {
+                payer,
+                entity,
+                component_program,
+                component,
+                system_program,
+                data,
+            } => {
+                let (payer, entity, component_program) = (
+                    self.extra(payer)?,
+                    self.extra(entity)?,
+                    self.extra(component_program)?,
+                );
+
+                let entity_data = Entity::from_account_info_mut(entity)?;
+
+                self.assert_entity_authority(entity_data, system)?;
+
+                let (component, system_program) =
+                    (self.extra(component)?, self.extra(system_program)?);
+
+                if data.is_empty() {
+                    hermes_cpi_interface::component::Initialize {
+                        payer,
+                        authority: self.authority,
+                        component_program: component_program.key(),
+                        data: component,
+                        entity,
+                        instruction_sysvar_account: self.instruction_sysvar_account,
+                        system_program,
+                    }
+                    .invoke()?;
+                } else {
+                    hermes_cpi_interface::component::InitializeWithData {
+                        payer,
+                        authority: self.authority,
+                        component_program: component_program.key(),
+                        data: component,
+                        entity,
+                        instruction_sysvar_account: self.instruction_sysvar_account,
+                        system_program,
+                        instruction_data: data,
+                    }
+                    .invoke()?;
+                }
+
+                entity_data.add_component(entity, payer, component_program.key())?;
+            }
+            SystemCommand::DestroyComponent {
+                receiver,
+                component_program,
+                component_program_data,
+                entity,
+                component,
+                system_program,
+            } => {
+                let (receiver, component_program, entity) = (
+                    self.extra(receiver)?,
+                    self.extra(component_program)?,
+                    self.extra(entity)?,
+                );
+
+                let entity_data = Entity::from_account_info_mut(entity)?;
+
+                self.assert_entity_authority(entity_data, system)?;
+
+                hermes_cpi_interface::component::Destroy {
+                    authority: self.authority,
+                    component_program_data: self.extra(component_program_data)?,
+                    component: self.extra(component)?,
+                    component_program: component_program.key(),
+                    receiver,
+                    entity,
+                    instruction_sysvar_account: self.instruction_sysvar_account,
+                    system_program: self.extra(system_program)?,
+                }
+                .invoke()?;
+
+                entity_data.remove_component(entity, receiver, component_program.key())?;
+            }
+            SystemCommand::EmitEvent { data } => {
+                sol_log_data(&[&world.to_le_bytes(), system.key(), data]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn stride(flags: u8) -> usize {
+    match flags & APPLY_FLAG_CONTEXT {
+        0 => 2,
+        _ => 3,
+    }
+}
+
+fn components<'a>(
+    accounts: &'a [AccountInfo],
+    stride: usize,
+) -> impl Iterator<Item = ApplyComponent<'a>> {
+    accounts
+        .chunks_exact(stride)
+        .map(|accounts| ApplyComponent {
+            program: &accounts[0],
+            component: &accounts[1],
+            entity: accounts.get(2),
+        })
+}
+
+impl ApplyComponent<'_> {
+    /// Checks that the entity belongs to `world` and that the component is its
+    /// component of `program`.
+    fn assert_entity(&self, world: u64) -> ProgramResult {
+        let Some(entity_acct) = self.entity else {
+            return Ok(());
+        };
+
+        let entity = Entity::from_account_info(entity_acct)?;
+
+        if entity.world != world {
+            return Err(WorldError::WorldAccountMismatch.into());
+        }
+
+        if !entity.has_components(
+            unsafe { entity_acct.borrow_data_unchecked() },
+            core::slice::from_ref(self.program.key()),
+        )? {
+            return Err(WorldError::ComponentNotFound.into());
+        }
+
+        Entity::assert_component_pda(entity_acct.key(), self.program.key(), self.component.key())
+    }
+}
+
+/// Splits the flags that may follow the Borsh-encoded system arguments of the
+/// legacy applies off `data`. Only `APPLY_FLAG_CONTEXT` is accepted.
+pub fn parse_legacy_flags(data: &[u8]) -> Result<(&[u8], u8), ProgramError> {
+    let (_, rest) = parse_args(data)?;
+    let args = &data[..data.len() - rest.len()];
+
+    match rest {
+        [] | [0] => Ok((args, 0)),
+        [APPLY_FLAG_CONTEXT] => Ok((args, APPLY_FLAG_CONTEXT)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Splits a Borsh `Vec<u8>` of system arguments off `data`.
+pub fn parse_args(data: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    if data.len() < core::mem::size_of::<u32>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (len, rest) = data.split_at(core::mem::size_of::<u32>());
+    let len = u32::from_le_bytes(unsafe { (len.as_ptr() as *const [u8; 4]).read() }) as usize;
+
+    if rest.len() < len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(rest.split_at(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestAccount, TestAccounts};
+    use pinocchio::pubkey::Pubkey;
+
+    fn accounts(len: u8) -> TestAccounts {
+        TestAccounts::new(
+            &(0..len)
+                .map(|i| TestAccount::new([i; 32], crate::ID, vec![]))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn parses_header() {
+        let (header, rest) = ApplyHeader::parse(&[APPLY_FLAG_SESSION, 2, 1, 3, 9, 9]).unwrap();
+
+        assert_eq!(header.flags, APPLY_FLAG_SESSION);
+        assert_eq!(header.components, 2);
+        assert_eq!(header.readonly_components, 1);
+        assert_eq!(header.extras, 3);
+        assert_eq!(rest, &[9, 9]);
+
+        let (header, rest) = ApplyHeader::parse(&[0; ApplyHeader::LEN]).unwrap();
+        assert_eq!(header.flags, 0);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn rejects_short_header() {
+        assert!(ApplyHeader::parse(&[]).is_err());
+        assert!(ApplyHeader::parse(&[0; ApplyHeader::LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let flags = APPLY_FLAG_SESSION | APPLY_FLAG_CONTEXT | APPLY_FLAG_BUFFER;
+
+        assert!(ApplyHeader::parse(&[flags, 0, 0, 0]).is_ok());
+        assert!(ApplyHeader::parse(&[flags + 1, 0, 0, 0]).is_err());
+        assert!(ApplyHeader::parse(&[1 << 7, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn parses_context() {
+        let accounts = accounts(11);
+        let (header, _) = ApplyHeader::parse(&[0, 2, 1, 1]).unwrap();
+
+        let (ctx, rest) = ApplyContext::parse(accounts.infos(), &header).unwrap();
+
+        assert!(ctx.session_token.is_none());
+        assert_eq!(ctx.writable_len(), 2);
+        assert_eq!(ctx.readonly_components[0].key(), &[7; 32]);
+        assert_eq!(ctx.extras[0].key(), &[9; 32]);
+        assert_eq!(rest.len(), 1);
+
+        let (header, _) = ApplyHeader::parse(&[APPLY_FLAG_SESSION, 2, 1, 0]).unwrap();
+
+        let (ctx, rest) = ApplyContext::parse(accounts.infos(), &header).unwrap();
+
+        assert_eq!(ctx.session_token.unwrap().key(), &[3; 32]);
+        assert_eq!(ctx.readonly().count(), 1);
+        assert!(ctx.extras.is_empty());
+        assert_eq!(rest.len(), 1);
+
+        let (header, _) = ApplyHeader::parse(&[0, 5, 0, 0]).unwrap();
+        assert!(ApplyContext::parse(accounts.infos(), &header).is_err());
+    }
+
+    #[test]
+    fn checks_component_entities() {
+        let (program, other_program) = ([7; 32], [8; 32]);
+        let accounts = TestAccounts::new(&[
+            TestAccount::new(program, Pubkey::default(), vec![]),
+            TestAccount::new([2; 32], program, vec![0; 16]),
+            TestAccount::entity([3; 32], 1, &[other_program, program]),
+            TestAccount::entity([4; 32], 1, &[other_program]),
+        ]);
+        let [program, component, entity, other_entity] = accounts.infos() else {
+            unreachable!()
+        };
+
+        let component = |entity| ApplyComponent {
+            program,
+            component,
+            entity: Some(entity),
+        };
+
+        assert_eq!(
+            component(entity).assert_entity(2),
+            Err(WorldError::WorldAccountMismatch.into())
+        );
+        assert_eq!(
+            component(other_entity).assert_entity(1),
+            Err(WorldError::ComponentNotFound.into())
+        );
+        // The entity has a component of `program`, but not this one.
+        assert_eq!(
+            component(entity).assert_entity(1),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn parses_legacy_triples() {
+        let accounts = TestAccounts::new(
+            &[[1; 32], [2; 32], [3; 32], crate::ID, [5; 32]]
+                .map(|key| TestAccount::new(key, Pubkey::default(), vec![])),
+        );
+        let [authority, sysvar, world, accounts @ ..] = accounts.infos() else {
+            unreachable!()
+        };
+
+        let ctx = ApplyContext::parse_legacy(authority, sysvar, world, None, accounts).unwrap();
+        assert!(ctx.has_context());
+        assert_eq!(ctx.writable_len(), 0);
+        assert_eq!(ctx.extras.len(), 1);
+
+        let ctx =
+            ApplyContext::parse_legacy(authority, sysvar, world, None, &accounts[..0]).unwrap();
+        assert!(ctx.extras.is_empty());
+
+        let triple = &[authority.clone(), sysvar.clone(), world.clone()];
+        let ctx = ApplyContext::parse_legacy(authority, sysvar, world, None, triple).unwrap();
+        assert_eq!(ctx.writable_len(), 1);
+        assert!(ctx.extras.is_empty());
+
+        assert!(ApplyContext::parse_legacy(authority, sysvar, world, None, &triple[..2]).is_err());
+    }
+
+    #[test]
+    fn splits_legacy_flags() {
+        let args = [1, 0, 0, 0, 7];
+
+        assert_eq!(parse_legacy_flags(&args).unwrap(), (&args[..], 0));
+        assert_eq!(
+            parse_legacy_flags(&[&args[..], &[APPLY_FLAG_CONTEXT]].concat()).unwrap(),
+            (&args[..], APPLY_FLAG_CONTEXT)
+        );
+        assert!(parse_legacy_flags(&[&args[..], &[APPLY_FLAG_SESSION]].concat()).is_err());
+        assert!(parse_legacy_flags(&[&args[..], &[APPLY_FLAG_CONTEXT, 0]].concat()).is_err());
+        assert!(parse_legacy_flags(&args[..4]).is_err());
+    }
+
+    #[test]
+    fn splits_args() {
+        let (args, rest) = parse_args(&[2, 0, 0, 0, 7, 8, 9]).unwrap();
+        assert_eq!(args, &[7, 8]);
+        assert_eq!(rest, &[9]);
+
+        assert!(parse_args(&[0, 0, 0]).is_err());
+        assert!(parse_args(&[3, 0, 0, 0, 7, 8]).is_err());
+    }
+}
@@ -1 +1,7 @@
 pub const DISCRIMATOR_LENGTH: usize = 8;
+
+/// Largest instruction data, discriminator excluded, of the `Update` CPI.
+pub const MAX_UPDATE_DATA_LEN: usize = 256;
+
+/// Share of a reaped entity's rent paid to the caller, in basis points.
+pub const REAP_BOUNTY_BPS: u64 = 500;
@@ -1,3 +1,4 @@
+use hermes_merkle::MerkleError;
 use pinocchio::program_error::ProgramError;
 
 pub enum WorldError {
@@ -7,10 +8,32 @@ pub enum WorldError {
     TooManyAuthorities,
     AuthorityNotFound,
     SystemNotApproved,
+    ComponentNotFound,
+    EntityHasComponents,
+    LegacyEntity,
+    MetadataTooLarge,
+    InvalidParent,
+    EntityHasParent,
+    EntityHasChildren,
+    ComponentAlreadyExists,
+    EntityNotExpired,
+    MigrationNotAllowed,
+    InvalidMerkleProof,
+    CompressedStoreFull,
+    UnexpectedAccounts,
 }
 
 impl From<WorldError> for ProgramError {
     fn from(e: WorldError) -> Self {
         ProgramError::Custom(e as u32)
     }
-}
\ No newline at end of file
+}
+
+impl From<MerkleError> for WorldError {
+    fn from(e: MerkleError) -> Self {
+        match e {
+            MerkleError::TreeFull => WorldError::CompressedStoreFull,
+            _ => WorldError::InvalidMerkleProof,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    error::WorldError,
+    state::compressed::{CompressedEntity, CompressedStore},
+};
+use pinocchio::{
+    account_info::AccountInfo, cpi::set_return_data, log::sol_log_data,
+    program_error::ProgramError, ProgramResult,
+};
+
+/// Appends an entity owned by `owner` to a compressed store. The entity record
+/// is logged for indexers and its id returned.
+pub fn add_compressed_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [owner, store_acct] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if data.len() != core::mem::size_of::<u64>() + 32 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (store, tree) = CompressedStore::from_account_info_mut(store_acct)?;
+
+    let (tags, data_hash) = data.split_at(core::mem::size_of::<u64>());
+
+    let entity = CompressedEntity {
+        id: tree.len() as u64,
+        owner: *owner.key(),
+        tags: u64::from_le_bytes(unsafe { (tags.as_ptr() as *const [u8; 8]).read() }),
+        data_hash: unsafe { (data_hash.as_ptr() as *const [u8; 32]).read() },
+    };
+
+    tree.append(entity.leaf(store.world))
+        .map_err(WorldError::from)?;
+
+    sol_log_data(&[&store.world.to_le_bytes(), &entity.to_bytes()]);
+    set_return_data(&entity.id.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::state::{
+    entity::{Entity, EntityKind},
+    transmutable::{Transmutable, TransmutableMut},
+    world::WorldMut,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub fn add_entities(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, world_acct, _system_program, entities @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (count, rest) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let count = *count as usize;
+    let expiry_slot = Entity::parse_expiry_slot(rest)?;
+
+    if count == 0 || entities.len() != count {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let world = WorldMut::from_account_info(world_acct)?;
+
+    let world_id = world.metadata.id.to_be_bytes();
+    let first_id = world.metadata.entities;
+
+    let lamports_needed = Rent::get()?.minimum_balance(Entity::LEN);
+
+    for (entity_acct, id) in entities.iter().zip(first_id..) {
+        let entity_id = id.to_be_bytes();
+
+        let (_, bump) = Entity::pda(&world_id, &entity_id, &[]);
+
+        CreateAccount {
+            from: payer,
+            to: entity_acct,
+            lamports: lamports_needed,
+            space: Entity::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Entity::signer(&world_id, &entity_id, &[], &[bump])
+            .as_slice()
+            .into()])?;
+
+        let entity =
+            unsafe { Entity::load_mut_unchecked(entity_acct.borrow_mut_data_unchecked())? };
+        entity.init(world.metadata.id, id, EntityKind::Counter, payer.key())?;
+        entity.expiry_slot = expiry_slot;
+    }
+
+    world.metadata.entities += count as u64;
+
+    set_return_data(&first_id.to_le_bytes());
+
+    Ok(())
+}
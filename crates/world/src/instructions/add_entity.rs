@@ -1,7 +1,11 @@
-use crate::state::{
-    entity::Entity,
-    transmutable::{Transmutable, TransmutableMut},
-    world::WorldMut,
+use crate::{
+    error::WorldError,
+    state::{
+        entity::{Entity, EntityKind, EntityName},
+        free_list::FreeList,
+        transmutable::{Transmutable, TransmutableMut},
+        world::WorldMut,
+    },
 };
 use pinocchio::{
     account_info::AccountInfo,
@@ -12,18 +16,60 @@ use pinocchio::{
 use pinocchio_system::instructions::CreateAccount;
 
 pub fn add_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [payer, entity_acct, world_acct, _system_program] = accounts else {
+    let [payer, entity_acct, world_acct, _system_program, free_list @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    let world = WorldMut::from_account_info(world_acct)?;
+    let mut world = WorldMut::from_account_info(world_acct)?;
 
-    let (_, bump) = Entity::pda(
-        &world.metadata.id.to_be_bytes(),
-        &world.metadata.entities.to_be_bytes(),
-        data,
+    let (name, rest) = EntityName::parse(data)?;
+    let expiry_slot = Entity::parse_expiry_slot(rest)?;
+
+    create_entity(
+        payer,
+        entity_acct,
+        &mut world,
+        name.as_ref(),
+        expiry_slot,
+        free_list.first(),
     )?;
 
+    Ok(())
+}
+
+#[allow(clippy::mut_from_ref)]
+pub fn create_entity<'a>(
+    payer: &AccountInfo,
+    entity_acct: &'a AccountInfo,
+    world: &mut WorldMut,
+    name: Option<&EntityName>,
+    expiry_slot: u64,
+    free_list: Option<&AccountInfo>,
+) -> Result<&'a mut Entity, ProgramError> {
+    let world_id = world.metadata.id.to_be_bytes();
+
+    let recycled_id = match (name, free_list) {
+        (None, Some(free_list_acct)) => {
+            let free_list = FreeList::from_account_info_mut(free_list_acct)?;
+
+            if free_list.world != world.metadata.id {
+                return Err(WorldError::WorldAccountMismatch.into());
+            }
+
+            free_list.pop(free_list_acct, payer)?
+        }
+        (None, None) | (Some(_), _) => None,
+    };
+
+    let id = recycled_id.unwrap_or(world.metadata.entities);
+
+    let (entity_id, name_seed) = match name {
+        Some(name) => (*name.id_seed(), name.as_seed()),
+        None => (id.to_be_bytes(), [].as_slice()),
+    };
+
+    let (_, bump) = Entity::pda(&world_id, &entity_id, name_seed);
+
     let lamports_needed = Rent::get()?.minimum_balance(Entity::LEN);
 
     CreateAccount {
@@ -33,19 +79,24 @@ pub fn add_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         space: Entity::LEN as u64,
         owner: &crate::ID,
     }
-    .invoke_signed(&[Entity::signer(
-        &world.metadata.id.to_be_bytes(),
-        &world.metadata.entities.to_be_bytes(),
-        data,
-        &[bump],
-    )?
-    .as_slice()
-    .into()])?;
+    .invoke_signed(&[Entity::signer(&world_id, &entity_id, name_seed, &[bump])
+        .as_slice()
+        .into()])?;
 
     let entity = unsafe { Entity::load_mut_unchecked(entity_acct.borrow_mut_data_unchecked())? };
-    entity.init(world.metadata.entities)?;
 
-    world.metadata.entities += 1;
+    match name {
+        Some(_) => entity.init(world.metadata.id, 0, EntityKind::Named, payer.key())?,
+        None => {
+            entity.init(world.metadata.id, id, EntityKind::Counter, payer.key())?;
 
-    Ok(())
+            if recycled_id.is_none() {
+                world.metadata.entities += 1;
+            }
+        }
+    }
+
+    entity.expiry_slot = expiry_slot;
+
+    Ok(entity)
 }
@@ -0,0 +1,55 @@
+use crate::state::{
+    entity::{Entity, EntityKind},
+    transmutable::{Transmutable, TransmutableMut},
+    world::WorldRef,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// Spawns an entity at a PDA of the payer and a client nonce. Unlike
+/// `add_entity`, the world account is only read so spawns don't contend on it.
+pub fn add_entity_with_nonce(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, entity_acct, world_acct, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if data.len() < core::mem::size_of::<u64>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (nonce, rest) = data.split_at(core::mem::size_of::<u64>());
+    let nonce = u64::from_le_bytes(unsafe { (nonce.as_ptr() as *const [u8; 8]).read() });
+    let expiry_slot = Entity::parse_expiry_slot(rest)?;
+
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    let world_id = world.metadata.id.to_be_bytes();
+    let nonce_seed = nonce.to_be_bytes();
+
+    let (_, bump) = Entity::nonce_pda(&world_id, payer.key(), &nonce_seed);
+
+    CreateAccount {
+        from: payer,
+        to: entity_acct,
+        lamports: Rent::get()?.minimum_balance(Entity::LEN),
+        space: Entity::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[
+        Entity::nonce_signer(&world_id, payer.key(), &nonce_seed, &[bump])
+            .as_slice()
+            .into(),
+    ])?;
+
+    let entity = unsafe { Entity::load_mut_unchecked(entity_acct.borrow_mut_data_unchecked())? };
+
+    entity.init(world.metadata.id, nonce, EntityKind::Nonce, payer.key())?;
+    entity.expiry_slot = expiry_slot;
+
+    Ok(())
+}
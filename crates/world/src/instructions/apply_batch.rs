@@ -0,0 +1,54 @@
+use crate::{
+    apply::{parse_args, ApplyContext, ApplyHeader},
+    error::WorldError,
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Applies one system to independent groups of components. The data is
+/// `[groups u8]` then, for each group, an `ApplyHeader` and the Borsh-encoded
+/// system arguments; every header must carry the same flags. The accounts are
+/// the system, those of the first group's `ApplyContext`, then the component
+/// and extra accounts of the following groups.
+pub fn apply_batch(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [system, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let [groups, data @ ..] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if *groups == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (header, data) = ApplyHeader::parse(data)?;
+    let (args, mut data) = parse_args(data)?;
+    let (mut ctx, mut remaining) = ApplyContext::parse(accounts, &header)?;
+
+    let world = ctx.authorize(core::slice::from_ref(system))?;
+
+    ctx.assert_entities(world)?;
+    ctx.execute(world, system, args)?;
+
+    for _ in 1..*groups {
+        let (header, rest) = ApplyHeader::parse(data)?;
+        let (args, rest) = parse_args(rest)?;
+        data = rest;
+
+        (ctx, remaining) = ctx.next_group(remaining, &header)?;
+
+        ctx.assert_entities(world)?;
+        ctx.execute(world, system, args)?;
+    }
+
+    if !remaining.is_empty() {
+        return Err(WorldError::UnexpectedAccounts.into());
+    }
+
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
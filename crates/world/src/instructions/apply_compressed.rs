@@ -0,0 +1,239 @@
+use crate::{
+    apply::parse_args,
+    error::WorldError,
+    state::{
+        compressed::{CompressedEntity, CompressedStore, CompressedTree, COMPRESSED_TREE_DEPTH},
+        world::WorldRef,
+    },
+    system_output::{ComponentOutput, SystemOutput},
+    utils::account_refs,
+};
+use core::mem::MaybeUninit;
+use hermes_merkle::Node;
+use pinocchio::{
+    account_info::AccountInfo, cpi::MAX_CPI_ACCOUNTS, log::sol_log_data,
+    program_error::ProgramError, ProgramResult,
+};
+
+/// Applies `system` to a compressed entity. `data` holds the root the proof
+/// was made against, the entity record, the proof, the Borsh `Vec<u8>` of
+/// entity data its data hash commits to, then the system arguments. The system
+/// is sent the entity data and the arguments with the extra accounts, and
+/// returns the new entity data as its only component output, whose hash
+/// replaces the data hash in the leaf. The owner or an approved system's
+/// authority must sign, as for `update_compressed_entity`.
+pub fn apply_compressed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [system, authority, store_acct, world_acct, extras @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (root, entity, proof, system_data) = parse(data)?;
+    let (entity_data, _) = parse_args(system_data)?;
+
+    if CompressedEntity::hash_data(entity_data) != entity.data_hash {
+        return Err(WorldError::InvalidMerkleProof.into());
+    }
+
+    let (store, tree) = CompressedStore::from_account_info_mut(store_acct)?;
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    if store.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if !world.is_system_approved(system.key())? {
+        return Err(WorldError::SystemNotApproved.into());
+    }
+
+    world.assert_entity_authority(&entity.owner, authority, Some(system))?;
+
+    const UNINIT_INFO: MaybeUninit<&AccountInfo> = MaybeUninit::uninit();
+
+    let mut extra_refs = [UNINIT_INFO; MAX_CPI_ACCOUNTS];
+
+    hermes_cpi_interface::system::Execute::new(
+        authority,
+        &[],
+        account_refs(extras.iter(), &mut extra_refs)?,
+        system.key(),
+        system_data,
+    )
+    .invoke()?;
+
+    let output = SystemOutput::get(system.key(), 1)?;
+    let output = output
+        .components()
+        .next()
+        .ok_or(WorldError::InvalidSystemOutput)?;
+
+    let entity = write_back(tree, store.world, root, entity, proof, &output)?;
+
+    sol_log_data(&[&store.world.to_le_bytes(), &entity.to_bytes()]);
+
+    Ok(())
+}
+
+/// Splits `[root][entity record][proof]` off `data`.
+#[allow(clippy::type_complexity)]
+fn parse(data: &[u8]) -> Result<(&Node, CompressedEntity, &[Node], &[u8]), ProgramError> {
+    const NODE_LEN: usize = core::mem::size_of::<Node>();
+
+    if data.len() < NODE_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (root, rest) = data.split_at(NODE_LEN);
+    let root = unsafe { &*(root.as_ptr() as *const Node) };
+
+    let (entity, rest) = CompressedEntity::parse(rest)?;
+
+    if rest.len() < COMPRESSED_TREE_DEPTH * NODE_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (proof, rest) = rest.split_at(COMPRESSED_TREE_DEPTH * NODE_LEN);
+    let proof = unsafe {
+        core::slice::from_raw_parts(proof.as_ptr() as *const Node, COMPRESSED_TREE_DEPTH)
+    };
+
+    Ok((root, entity, proof, rest))
+}
+
+/// Replaces the leaf of `entity` by one committing to the new entity data in
+/// `output`, returning the updated record.
+fn write_back(
+    tree: &mut CompressedTree,
+    world: u64,
+    root: &Node,
+    mut entity: CompressedEntity,
+    proof: &[Node],
+    output: &ComponentOutput,
+) -> Result<CompressedEntity, ProgramError> {
+    let previous_leaf = entity.leaf(world);
+
+    match output {
+        ComponentOutput::State(state) => {
+            entity.data_hash = CompressedEntity::hash_data(&state[core::mem::size_of::<u32>()..])
+        }
+        ComponentOutput::Unchanged => {}
+        _ => return Err(WorldError::InvalidSystemOutput.into()),
+    }
+
+    let index = u32::try_from(entity.id).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    tree.set_leaf(root, &previous_leaf, &entity.leaf(world), proof, index)
+        .map_err(WorldError::from)?;
+
+    Ok(entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hermes_merkle::MerkleTree;
+
+    fn entity(data: &[u8]) -> CompressedEntity {
+        CompressedEntity {
+            id: 1,
+            owner: [1; 32],
+            tags: 0,
+            data_hash: CompressedEntity::hash_data(data),
+        }
+    }
+
+    #[test]
+    fn parses_data() {
+        let mut data = vec![3; 32];
+        data.extend(entity(&[]).to_bytes());
+        data.extend([4; COMPRESSED_TREE_DEPTH * 32]);
+        data.extend([1, 0, 0, 0, 5]);
+
+        let (root, parsed, proof, rest) = parse(&data).unwrap();
+
+        assert_eq!(root, &[3; 32]);
+        assert_eq!(parsed.to_bytes(), entity(&[]).to_bytes());
+        assert_eq!(proof, &[[4; 32]; COMPRESSED_TREE_DEPTH]);
+        assert_eq!(rest, &[1, 0, 0, 0, 5]);
+
+        assert!(parse(&data[..data.len() - 6]).is_err());
+        assert!(parse(&data[..31]).is_err());
+    }
+
+    #[test]
+    fn writes_back_new_data_hash() {
+        let world = 7;
+        let mut tree = Box::new(CompressedTree::new());
+        let mut host = MerkleTree::new(COMPRESSED_TREE_DEPTH);
+
+        for entity in [entity(&[9]), entity(&[1, 2])]
+            .into_iter()
+            .enumerate()
+            .map(|(id, entity)| CompressedEntity {
+                id: id as u64,
+                ..entity
+            })
+        {
+            tree.append(entity.leaf(world)).unwrap();
+            host.append(entity.leaf(world));
+        }
+
+        let root = host.root();
+        let proof = host.proof(1);
+        let state = [2, 0, 0, 0, 3, 4];
+
+        let updated = write_back(
+            &mut tree,
+            world,
+            &root,
+            entity(&[1, 2]),
+            &proof,
+            &ComponentOutput::State(&state),
+        )
+        .unwrap();
+
+        assert_eq!(updated.data_hash, CompressedEntity::hash_data(&[3, 4]));
+
+        host.set(1, updated.leaf(world));
+        assert_eq!(tree.root(), host.root());
+
+        // The proof was made against a leaf that has since been replaced.
+        assert!(write_back(
+            &mut tree,
+            world,
+            &root,
+            entity(&[1, 2]),
+            &proof,
+            &ComponentOutput::Unchanged,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_patches_and_buffers() {
+        let world = 7;
+        let mut tree = Box::new(CompressedTree::new());
+        let mut host = MerkleTree::new(COMPRESSED_TREE_DEPTH);
+
+        let entity = CompressedEntity {
+            id: 0,
+            ..entity(&[])
+        };
+        tree.append(entity.leaf(world)).unwrap();
+        host.append(entity.leaf(world));
+
+        for output in [
+            ComponentOutput::Patch(&[0, 0, 0, 0]),
+            ComponentOutput::Buffer { offset: 8, len: 1 },
+        ] {
+            assert!(write_back(
+                &mut tree,
+                world,
+                &host.root(),
+                CompressedEntity { ..entity },
+                &host.proof(0),
+                &output,
+            )
+            .is_err());
+        }
+    }
+}
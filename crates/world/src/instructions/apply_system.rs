@@ -1,18 +1,41 @@
-use crate::{error::WorldError, state::world::WorldRef, utils::init_execute_cpi_accounts};
+use crate::{
+    apply::{parse_legacy_flags, ApplyContext, APPLY_FLAG_CONTEXT},
+    error::WorldError,
+    state::world::WorldRef,
+    system_output::SystemOutput,
+    utils::init_execute_cpi_accounts,
+};
 use core::mem::MaybeUninit;
 use pinocchio::{
-    account_info::AccountInfo,
-    cpi::{get_return_data, MAX_CPI_ACCOUNTS},
-    program_error::ProgramError,
-    ProgramResult,
+    account_info::AccountInfo, cpi::MAX_CPI_ACCOUNTS, program_error::ProgramError, ProgramResult,
 };
 
+/// Applies `system` to the `(component_program, component)` pairs in
+/// `remaining`. With `APPLY_FLAG_CONTEXT` after the arguments, `remaining`
+/// holds `(component_program, component, entity)` triples instead and the
+/// system is sent an `ExecutionContext`, running as it would in `apply_v2`.
 pub fn apply_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let [system, authority, instruction_sysvar_account, world_acct, remaining @ ..] = accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let (data, flags) = parse_legacy_flags(data)?;
+
+    if flags & APPLY_FLAG_CONTEXT != 0 {
+        let ctx = ApplyContext::parse_legacy(
+            authority,
+            instruction_sysvar_account,
+            world_acct,
+            None,
+            remaining,
+        )?;
+        let world = ctx.authorize(core::slice::from_ref(system))?;
+
+        ctx.assert_entities(world)?;
+        return ctx.execute(world, system, data);
+    }
+
     if !authority.is_signer() && authority.key() != &crate::ID {
         return Err(WorldError::InvalidAuthority.into());
     }
@@ -30,47 +53,32 @@ pub fn apply_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let (components, sep_idx, remaining_accounts) =
         init_execute_cpi_accounts(remaining, &mut ctx_accounts)?;
 
-    hermes_cpi_interface::system::Execute {
+    hermes_cpi_interface::system::Execute::new(
         authority,
         components,
         remaining_accounts,
-        instruction_data: data,
-        system: system.key(),
-    }
+        system.key(),
+        data,
+    )
     .invoke()?;
 
-    let return_data = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
-
     let components_pair = &remaining[..sep_idx.unwrap_or(remaining.len())];
 
-    let (_, data) = return_data.as_slice().split_at(core::mem::size_of::<u32>());
-
-    let mut cursor = 0;
+    let output = SystemOutput::get(system.key(), components_pair.len() / 2)?;
 
-    for pair in components_pair.chunks_exact(2) {
+    for (pair, output) in components_pair.chunks_exact(2).zip(output.components()) {
         let [component_program, component] = pair else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        let mut size = core::mem::size_of::<u32>();
-
-        let len_bytes = &data[cursor..cursor + size];
-
-        let len =
-            u32::from_le_bytes(unsafe { (len_bytes.as_ptr() as *const [u8; 4]).read() }) as usize;
-
-        size += len;
-
-        hermes_cpi_interface::component::Update {
+        output.write_back(
             authority,
+            component_program,
             component,
-            component_program: component_program.key(),
-            instruction_data: &data[cursor..cursor + size],
             instruction_sysvar_account,
-        }
-        .invoke()?;
-
-        cursor += size;
+            None,
+            None,
+        )?;
     }
 
     Ok(())
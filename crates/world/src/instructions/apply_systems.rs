@@ -0,0 +1,46 @@
+use crate::{
+    apply::{parse_args, ApplyContext, ApplyHeader},
+    error::WorldError,
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Applies an ordered list of systems to the same components, each one reading
+/// what the previous one wrote back. The data is `[systems u8]`, an
+/// `ApplyHeader` and the Borsh-encoded arguments of each system; the accounts
+/// are the systems then those of the `ApplyContext`.
+pub fn apply_systems(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [systems, data @ ..] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    let systems = *systems as usize;
+
+    if systems == 0 || accounts.len() < systems {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let (systems, accounts) = accounts.split_at(systems);
+    let (header, mut data) = ApplyHeader::parse(data)?;
+    let (ctx, remaining) = ApplyContext::parse(accounts, &header)?;
+
+    if !remaining.is_empty() {
+        return Err(WorldError::UnexpectedAccounts.into());
+    }
+
+    let world = ctx.authorize(systems)?;
+
+    ctx.assert_entities(world)?;
+
+    for system in systems {
+        let (args, rest) = parse_args(data)?;
+        data = rest;
+
+        ctx.execute(world, system, args)?;
+    }
+
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
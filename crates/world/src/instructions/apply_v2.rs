@@ -0,0 +1,25 @@
+use crate::{
+    apply::{ApplyContext, ApplyHeader},
+    error::WorldError,
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Applies a system described by an `ApplyHeader`, followed by the system
+/// arguments. The accounts are the system then those of the `ApplyContext`.
+pub fn apply_v2(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [system, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (header, args) = ApplyHeader::parse(data)?;
+    let (ctx, remaining) = ApplyContext::parse(accounts, &header)?;
+
+    if !remaining.is_empty() {
+        return Err(WorldError::UnexpectedAccounts.into());
+    }
+
+    let world = ctx.authorize(core::slice::from_ref(system))?;
+
+    ctx.assert_entities(world)?;
+    ctx.execute(world, system, args)
+}
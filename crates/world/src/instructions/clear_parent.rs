@@ -0,0 +1,28 @@
+use crate::{error::WorldError, state::entity::Entity};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+pub fn clear_parent(accounts: &[AccountInfo]) -> ProgramResult {
+    let [owner, child_acct, parent_acct] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let child = Entity::from_account_info_mut(child_acct)?;
+    let parent = Entity::from_account_info_mut(parent_acct)?;
+
+    if &child.parent != parent_acct.key() {
+        return Err(WorldError::InvalidParent.into());
+    }
+
+    if &child.owner != owner.key() && &parent.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    child.parent = Pubkey::default();
+    parent.remove_child()
+}
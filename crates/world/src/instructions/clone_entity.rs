@@ -0,0 +1,186 @@
+use crate::{
+    consts::{DISCRIMATOR_LENGTH, MAX_UPDATE_DATA_LEN},
+    error::WorldError,
+    instructions::create_entity,
+    state::{
+        entity::{Entity, EntityName},
+        world::WorldMut,
+    },
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+pub fn clone_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, source_acct, entity_acct, world_acct, authority, instruction_sysvar_account, system_program, components @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() && authority.key() != &crate::ID {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if components.len() % 3 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    let source = Entity::from_account_info(source_acct)?;
+
+    if source.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if &source.owner != payer.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let (name, rest) = EntityName::parse(data)?;
+    let expiry_slot = Entity::parse_expiry_slot(rest)?;
+
+    let entity = create_entity(
+        payer,
+        entity_acct,
+        &mut world,
+        name.as_ref(),
+        expiry_slot,
+        None,
+    )?;
+
+    entity.tags = source.tags;
+
+    for triple in components.chunks_exact(3) {
+        let [component_program, source_component, component] = triple else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !source.has_components(
+            unsafe { source_acct.borrow_data_unchecked() },
+            core::slice::from_ref(component_program.key()),
+        )? {
+            return Err(WorldError::ComponentNotFound.into());
+        }
+
+        clone_component(
+            payer,
+            authority,
+            component_program,
+            source_acct,
+            source_component,
+            component,
+            entity_acct,
+            instruction_sysvar_account,
+            system_program,
+        )?;
+
+        entity.add_component(entity_acct, payer, component_program.key())?;
+    }
+
+    Ok(())
+}
+
+/// Initializes `component` for `entity_acct` and copies the data of
+/// `source_component`, which must be the component of `component_program`
+/// attached to `source_acct`, into it through an `Update` CPI. Like
+/// `initialize_component`, the component program itself is not vetted here.
+#[allow(clippy::too_many_arguments)]
+pub fn clone_component(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    component_program: &AccountInfo,
+    source_acct: &AccountInfo,
+    source_component: &AccountInfo,
+    component: &AccountInfo,
+    entity_acct: &AccountInfo,
+    instruction_sysvar_account: &AccountInfo,
+    system_program: &AccountInfo,
+) -> ProgramResult {
+    if !source_component.is_owned_by(component_program.key()) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Entity::assert_component_pda(
+        source_acct.key(),
+        component_program.key(),
+        source_component.key(),
+    )?;
+
+    let source_data = unsafe { source_component.borrow_data_unchecked() }
+        .get(DISCRIMATOR_LENGTH..)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    let len = core::mem::size_of::<u32>() + source_data.len();
+
+    if len > MAX_UPDATE_DATA_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut instruction_data = [0u8; MAX_UPDATE_DATA_LEN];
+    instruction_data[..core::mem::size_of::<u32>()]
+        .copy_from_slice(&(source_data.len() as u32).to_le_bytes());
+    instruction_data[core::mem::size_of::<u32>()..len].copy_from_slice(source_data);
+
+    hermes_cpi_interface::component::Initialize {
+        payer,
+        authority,
+        component_program: component_program.key(),
+        data: component,
+        entity: entity_acct,
+        instruction_sysvar_account,
+        system_program,
+    }
+    .invoke()?;
+
+    hermes_cpi_interface::component::Update {
+        authority,
+        component,
+        component_program: component_program.key(),
+        instruction_data: &instruction_data[..len],
+        instruction_sysvar_account,
+    }
+    .invoke()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestAccount, TestAccounts};
+
+    #[test]
+    fn rejects_foreign_source_components() {
+        let (component_program, source_component) = ([2; 32], [4; 32]);
+        let accounts = TestAccounts::new(
+            &(0..9u8)
+                .map(|key| match [key; 32] {
+                    key if key == source_component => {
+                        TestAccount::new(key, component_program, vec![0; 16])
+                    }
+                    key => TestAccount::new(key, crate::ID, vec![]),
+                })
+                .collect::<Vec<_>>(),
+        );
+        let [payer, authority, component_program, source_acct, source_component, component, entity_acct, instruction_sysvar_account, system_program] =
+            accounts.infos()
+        else {
+            unreachable!()
+        };
+
+        let clone = |component_program| {
+            clone_component(
+                payer,
+                authority,
+                component_program,
+                source_acct,
+                source_component,
+                component,
+                entity_acct,
+                instruction_sysvar_account,
+                system_program,
+            )
+        };
+
+        assert_eq!(clone(payer), Err(ProgramError::IllegalOwner));
+        assert_eq!(clone(component_program), Err(ProgramError::InvalidSeeds));
+    }
+}
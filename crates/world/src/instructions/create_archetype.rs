@@ -0,0 +1,73 @@
+use crate::{
+    error::WorldError,
+    state::{
+        archetype::{Archetype, ArchetypeComponents, ArchetypeMetadata},
+        transmutable::{Transmutable, TransmutableMut},
+        world::WorldMut,
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub fn create_archetype(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, archetype_acct, world_acct, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    if !world.authorities()?.contains(authority.key()) {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    const HEADER_LEN: usize = core::mem::size_of::<u64>() + core::mem::size_of::<u32>();
+
+    if data.len() < HEADER_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (header, components) = data.split_at(HEADER_LEN);
+
+    let archetype_id = u64::from_le_bytes(unsafe { (header.as_ptr() as *const [u8; 8]).read() });
+    let count =
+        u32::from_le_bytes(unsafe { (header.as_ptr().add(8) as *const [u8; 4]).read() }) as u64;
+
+    ArchetypeComponents::validate(components, count)?;
+
+    let world_id = world.metadata.id.to_be_bytes();
+    let archetype_seed = archetype_id.to_be_bytes();
+
+    let (_, bump) = Archetype::pda(&world_id, &archetype_seed);
+
+    let space = Archetype::size(components);
+
+    CreateAccount {
+        from: authority,
+        to: archetype_acct,
+        lamports: Rent::get()?.minimum_balance(space),
+        space: space as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[Archetype::signer(&world_id, &archetype_seed, &[bump])
+        .as_slice()
+        .into()])?;
+
+    let (metadata_bytes, components_bytes) =
+        unsafe { archetype_acct.borrow_mut_data_unchecked() }.split_at_mut(ArchetypeMetadata::LEN);
+
+    let metadata = unsafe { ArchetypeMetadata::load_mut_unchecked(metadata_bytes)? };
+    metadata.init(world.metadata.id, archetype_id, count)?;
+
+    components_bytes.copy_from_slice(components);
+
+    Ok(())
+}
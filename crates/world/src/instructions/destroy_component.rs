@@ -1,3 +1,4 @@
+use crate::state::entity::Entity;
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
 pub fn destroy_component(accounts: &[AccountInfo]) -> ProgramResult {
@@ -7,6 +8,8 @@ pub fn destroy_component(accounts: &[AccountInfo]) -> ProgramResult {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let entity_data = Entity::from_account_info_mut(entity)?;
+
     hermes_cpi_interface::component::Destroy {
         authority,
         component_program_data,
@@ -17,5 +20,7 @@ pub fn destroy_component(accounts: &[AccountInfo]) -> ProgramResult {
         instruction_sysvar_account,
         system_program,
     }
-    .invoke()
+    .invoke()?;
+
+    entity_data.remove_component(entity, receiver, component_program.key())
 }
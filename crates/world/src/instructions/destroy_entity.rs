@@ -0,0 +1,249 @@
+use crate::{
+    error::WorldError,
+    state::{
+        entity::{Entity, EntityKind},
+        free_list::FreeList,
+    },
+    utils::close_account,
+};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+/// What happens to the children of a destroyed entity.
+#[repr(u8)]
+pub enum ChildPolicy {
+    /// Children become root entities.
+    Orphan,
+    /// Children are closed as well; they must have no components or children.
+    Destroy,
+}
+
+impl TryFrom<&[u8]> for ChildPolicy {
+    type Error = ProgramError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        match data {
+            [] | [0] => Ok(ChildPolicy::Orphan),
+            [1] => Ok(ChildPolicy::Destroy),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+pub fn destroy_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [owner, authority, entity_acct, instruction_sysvar_account, system_program, remaining @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let policy = ChildPolicy::try_from(data)?;
+
+    let entity = Entity::from_account_info_mut(entity_acct)?;
+
+    if &entity.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let free_list = release_entity(
+        entity,
+        entity_acct,
+        owner,
+        authority,
+        instruction_sysvar_account,
+        system_program,
+        remaining,
+        policy,
+    )?;
+
+    recycle_entity_id(entity, free_list, owner)?;
+
+    close_account(entity_acct, owner)
+}
+
+/// Pushes the id of a counter-based entity onto the world free list, if one
+/// was passed.
+pub fn recycle_entity_id(
+    entity: &Entity,
+    free_list_acct: Option<&AccountInfo>,
+    payer: &AccountInfo,
+) -> ProgramResult {
+    let Some(free_list_acct) = free_list_acct else {
+        return Ok(());
+    };
+
+    let free_list = FreeList::from_account_info_mut(free_list_acct)?;
+
+    if free_list.world != entity.world {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if entity.kind()? == EntityKind::Counter {
+        free_list.push(free_list_acct, payer, entity.id)?;
+    }
+
+    Ok(())
+}
+
+/// Destroys the components of an entity and detaches or closes its children,
+/// leaving the entity ready to be closed. `remaining` holds one
+/// `(component_program, component_program_data, component)` triple per
+/// component, then every child of the entity, then optionally the world free
+/// list, which is returned. Ids of counter-based children closed under
+/// `ChildPolicy::Destroy` are pushed onto that free list, paid by `owner`.
+#[allow(clippy::too_many_arguments)]
+pub fn release_entity<'a>(
+    entity: &mut Entity,
+    entity_acct: &AccountInfo,
+    owner: &AccountInfo,
+    authority: &AccountInfo,
+    instruction_sysvar_account: &AccountInfo,
+    system_program: &AccountInfo,
+    remaining: &'a [AccountInfo],
+    policy: ChildPolicy,
+) -> Result<Option<&'a AccountInfo>, ProgramError> {
+    if entity.has_parent() {
+        return Err(WorldError::EntityHasParent.into());
+    }
+
+    let (components, children, free_list) = split_release_accounts(entity, remaining)?;
+
+    for triple in components.chunks_exact(3) {
+        let [component_program, component_program_data, component] = triple else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        hermes_cpi_interface::component::Destroy {
+            authority,
+            component_program_data,
+            component,
+            component_program: component_program.key(),
+            receiver: owner,
+            entity: entity_acct,
+            instruction_sysvar_account,
+            system_program,
+        }
+        .invoke()?;
+
+        entity.remove_component(entity_acct, owner, component_program.key())?;
+    }
+
+    if entity.components != 0 {
+        return Err(WorldError::EntityHasComponents.into());
+    }
+
+    for child_acct in children {
+        let child = Entity::from_account_info_mut(child_acct)?;
+
+        if &child.parent != entity_acct.key() {
+            return Err(WorldError::InvalidParent.into());
+        }
+
+        match policy {
+            ChildPolicy::Orphan => child.parent = Pubkey::default(),
+            ChildPolicy::Destroy => {
+                if &child.owner != owner.key() {
+                    return Err(WorldError::InvalidAuthority.into());
+                }
+
+                if child.components != 0 {
+                    return Err(WorldError::EntityHasComponents.into());
+                }
+
+                if child.children != 0 {
+                    return Err(WorldError::EntityHasChildren.into());
+                }
+
+                recycle_entity_id(child, free_list, owner)?;
+
+                close_account(child_acct, owner)?;
+            }
+        }
+
+        entity.remove_child()?;
+    }
+
+    Ok(free_list)
+}
+
+/// Splits `remaining` into the `(component_program, component_program_data,
+/// component)` triples of `entity`'s components, its children and the optional
+/// free list.
+#[allow(clippy::type_complexity)]
+fn split_release_accounts<'a>(
+    entity: &Entity,
+    remaining: &'a [AccountInfo],
+) -> Result<
+    (
+        &'a [AccountInfo],
+        &'a [AccountInfo],
+        Option<&'a AccountInfo>,
+    ),
+    ProgramError,
+> {
+    let components_len = (entity.components as usize)
+        .checked_mul(3)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let (components, rest) = remaining
+        .split_at_checked(components_len)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let (children, free_list) = rest
+        .split_at_checked(entity.children as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let free_list = match free_list {
+        [] => None,
+        [free_list] => Some(free_list),
+        _ => return Err(ProgramError::InvalidArgument),
+    };
+
+    Ok((components, children, free_list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestAccount, TestAccounts};
+
+    #[test]
+    fn splits_release_accounts() {
+        let accounts = TestAccounts::new(
+            &(0..6u8)
+                .map(|key| TestAccount::new([key; 32], crate::ID, vec![]))
+                .collect::<Vec<_>>(),
+        );
+        let accounts = accounts.infos();
+
+        let mut entity: Entity = unsafe { core::mem::zeroed() };
+        entity.components = 1;
+        entity.children = 2;
+
+        let (components, children, free_list) =
+            split_release_accounts(&entity, &accounts[..5]).unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(children[0].key(), &[3; 32]);
+        assert_eq!(children.len(), 2);
+        assert!(free_list.is_none());
+
+        let (_, _, free_list) = split_release_accounts(&entity, accounts).unwrap();
+        assert_eq!(free_list.unwrap().key(), &[5; 32]);
+
+        assert_eq!(
+            split_release_accounts(&entity, &accounts[..4]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
+
+        entity.children = 1;
+        assert_eq!(
+            split_release_accounts(&entity, accounts).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+    }
+}
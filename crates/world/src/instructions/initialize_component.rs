@@ -1,8 +1,10 @@
-use crate::error::WorldError;
+use crate::{error::WorldError, state::entity::Entity};
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
-pub fn initialize_component(accounts: &[AccountInfo]) -> ProgramResult {
-    let [payer, data, entity, component_program, authority, instruction_sysvar_account, system_program] =
+/// Initializes a component of `entity`. Non-empty `data` is forwarded to the
+/// component program through `InitializeWithData` as its initial state.
+pub fn initialize_component(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, component, entity, component_program, authority, instruction_sysvar_account, system_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -12,14 +14,32 @@ pub fn initialize_component(accounts: &[AccountInfo]) -> ProgramResult {
         return Err(WorldError::InvalidAuthority.into());
     }
 
-    hermes_cpi_interface::component::Initialize {
-        payer,
-        authority,
-        component_program: component_program.key(),
-        data,
-        entity,
-        instruction_sysvar_account,
-        system_program,
+    let entity_data = Entity::from_account_info_mut(entity)?;
+
+    if data.is_empty() {
+        hermes_cpi_interface::component::Initialize {
+            payer,
+            authority,
+            component_program: component_program.key(),
+            data: component,
+            entity,
+            instruction_sysvar_account,
+            system_program,
+        }
+        .invoke()?;
+    } else {
+        hermes_cpi_interface::component::InitializeWithData {
+            payer,
+            authority,
+            component_program: component_program.key(),
+            data: component,
+            entity,
+            instruction_sysvar_account,
+            system_program,
+            instruction_data: data,
+        }
+        .invoke()?;
     }
-    .invoke()
+
+    entity_data.add_component(entity, payer, component_program.key())
 }
@@ -0,0 +1,36 @@
+use crate::{
+    error::WorldError,
+    state::{account::AnchorAccount, compressed::CompressedStore, world::WorldRef},
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+pub fn initialize_compressed_store(accounts: &[AccountInfo]) -> ProgramResult {
+    let [authority, store_acct, world_acct] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    if !world.authorities.contains(authority.key()) {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if !store_acct.is_owned_by(&crate::ID) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let (store, tree) = unsafe { CompressedStore::load_unchecked_with_tree(store_acct)? };
+
+    if store.discriminator() != [0; 8] {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    store.init(world.metadata.id)?;
+    tree.initialize();
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::{
+    error::WorldError,
+    state::{
+        free_list::FreeList,
+        transmutable::{Transmutable, TransmutableMut},
+        world::WorldMut,
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub fn initialize_free_list(accounts: &[AccountInfo]) -> ProgramResult {
+    let [authority, free_list_acct, world_acct, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    if !world.authorities()?.contains(authority.key()) {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let world_id = world.metadata.id.to_be_bytes();
+
+    let (_, bump) = FreeList::pda(&world_id);
+
+    CreateAccount {
+        from: authority,
+        to: free_list_acct,
+        lamports: Rent::get()?.minimum_balance(FreeList::LEN),
+        space: FreeList::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(&[FreeList::signer(&world_id, &[bump]).as_slice().into()])?;
+
+    let free_list =
+        unsafe { FreeList::load_mut_unchecked(free_list_acct.borrow_mut_data_unchecked())? };
+
+    free_list.init(world.metadata.id)
+}
@@ -0,0 +1,47 @@
+use crate::{
+    error::WorldError,
+    state::{
+        output_buffer::OutputBuffer,
+        transmutable::{Transmutable, TransmutableMut},
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+/// Creates the `OutputBuffer` record letting `system` return component states
+/// through its buffer when applied by `authority`.
+pub fn initialize_output_buffer(accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, authority, output_buffer_acct, system, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() && authority.key() != &crate::ID {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let (_, bump) = OutputBuffer::pda(system.key(), authority.key());
+
+    CreateAccount {
+        from: payer,
+        to: output_buffer_acct,
+        lamports: Rent::get()?.minimum_balance(OutputBuffer::LEN),
+        space: OutputBuffer::LEN as u64,
+        owner: &crate::ID,
+    }
+    .invoke_signed(
+        &[OutputBuffer::signer(system.key(), authority.key(), &[bump])
+            .as_slice()
+            .into()],
+    )?;
+
+    let output_buffer = unsafe {
+        OutputBuffer::load_mut_unchecked(output_buffer_acct.borrow_mut_data_unchecked())?
+    };
+
+    output_buffer.init(system.key(), authority.key())
+}
@@ -0,0 +1,132 @@
+use crate::{
+    error::WorldError,
+    instructions::{clone_component, create_entity},
+    state::{
+        entity::{Entity, EntityName},
+        migration_policy::MigrationPolicy,
+        world::{WorldMut, WorldRef},
+    },
+    utils::{close_account, resize_account},
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Moves an entity to another world: it is re-created there with copies of
+/// all its components, tags and metadata, and the source entity is closed.
+pub fn migrate_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [owner, source_acct, entity_acct, source_world_acct, source_policy_acct, world_acct, policy_acct, authority, instruction_sysvar_account, system_program, components @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let source = Entity::from_account_info_mut(source_acct)?;
+
+    if &source.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if source.has_parent() {
+        return Err(WorldError::EntityHasParent.into());
+    }
+
+    if source.children != 0 {
+        return Err(WorldError::EntityHasChildren.into());
+    }
+
+    if components.len() % 4 != 0 || components.len() as u64 / 4 != source.components {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let source_world = WorldRef::from_account_info(source_world_acct)?;
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    if source.world != source_world.metadata.id || source.world == world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    let source_policy = MigrationPolicy::from_account_info_mut(source_policy_acct)?;
+    let policy = MigrationPolicy::from_account_info_mut(policy_acct)?;
+
+    if source_policy.world != source.world || policy.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if !source_policy.allows_out() || !policy.allows_in() {
+        return Err(WorldError::MigrationNotAllowed.into());
+    }
+
+    let (name, rest) = EntityName::parse(data)?;
+
+    if !rest.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let entity = create_entity(
+        owner,
+        entity_acct,
+        &mut world,
+        name.as_ref(),
+        source.expiry_slot,
+        None,
+    )?;
+
+    entity.tags = source.tags;
+
+    for quad in components.chunks_exact(4) {
+        let [component_program, component_program_data, source_component, component] = quad else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        clone_component(
+            owner,
+            authority,
+            component_program,
+            source_acct,
+            source_component,
+            component,
+            entity_acct,
+            instruction_sysvar_account,
+            system_program,
+        )?;
+
+        entity.add_component(entity_acct, owner, component_program.key())?;
+
+        hermes_cpi_interface::component::Destroy {
+            authority,
+            component_program_data,
+            component: source_component,
+            component_program: component_program.key(),
+            receiver: owner,
+            entity: source_acct,
+            instruction_sysvar_account,
+            system_program,
+        }
+        .invoke()?;
+
+        source.remove_component(source_acct, owner, component_program.key())?;
+    }
+
+    if source.metadata_len != 0 {
+        let offset = entity.metadata_offset();
+
+        resize_account(
+            entity_acct,
+            owner,
+            entity.size() + source.metadata_len as usize,
+        )?;
+
+        let source_data = unsafe { source_acct.borrow_data_unchecked() };
+        let data = unsafe { entity_acct.borrow_mut_data_unchecked() };
+        data[offset..].copy_from_slice(&source_data[source.metadata_offset()..source.size()]);
+
+        entity.metadata_len = source.metadata_len;
+    }
+
+    source_policy.record_migration(policy)?;
+
+    close_account(source_acct, owner)
+}
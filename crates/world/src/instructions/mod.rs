@@ -1,36 +1,108 @@
 mod add_authority;
 pub use add_authority::*;
 
+mod add_compressed_entity;
+pub use add_compressed_entity::*;
+
+mod add_entities;
+pub use add_entities::*;
+
 mod add_entity;
 pub use add_entity::*;
 
+mod add_entity_with_nonce;
+pub use add_entity_with_nonce::*;
+
+mod apply_batch;
+pub use apply_batch::*;
+
+mod apply_compressed;
+pub use apply_compressed::*;
+
 mod apply_system;
 pub use apply_system::*;
 
 mod apply_system_session;
 pub use apply_system_session::*;
 
+mod apply_systems;
+pub use apply_systems::*;
+
+mod apply_v2;
+pub use apply_v2::*;
+
 mod approve_system;
 pub use approve_system::*;
 
+mod clear_parent;
+pub use clear_parent::*;
+
+mod clone_entity;
+pub use clone_entity::*;
+
+mod create_archetype;
+pub use create_archetype::*;
+
 mod destroy_component;
 pub use destroy_component::*;
 
+mod destroy_entity;
+pub use destroy_entity::*;
+
 mod initialize_component;
 pub use initialize_component::*;
 
+mod initialize_compressed_store;
+pub use initialize_compressed_store::*;
+
+mod initialize_free_list;
+pub use initialize_free_list::*;
+
 mod initialize_registry;
 pub use initialize_registry::*;
 
 mod initialize_new_world;
 pub use initialize_new_world::*;
 
+mod initialize_output_buffer;
+pub use initialize_output_buffer::*;
+
+mod migrate_entity;
+pub use migrate_entity::*;
+
+mod reap_entity;
+pub use reap_entity::*;
+
 mod remove_authority;
 pub use remove_authority::*;
 
 mod remove_system;
 pub use remove_system::*;
 
+mod resize_component;
+pub use resize_component::*;
+
+mod set_entity_metadata;
+pub use set_entity_metadata::*;
+
+mod set_entity_tags;
+pub use set_entity_tags::*;
+
+mod set_migration_policy;
+pub use set_migration_policy::*;
+
+mod set_parent;
+pub use set_parent::*;
+
+mod spawn_archetype;
+pub use spawn_archetype::*;
+
+mod update_compressed_entity;
+pub use update_compressed_entity::*;
+
+mod upgrade_entity;
+pub use upgrade_entity::*;
+
 use pinocchio::program_error::ProgramError;
 
 pub const INITIALIZE_REGISTRY_DISCRIMINATOR: u64 = 4321548737212364221;
@@ -44,6 +116,30 @@ pub const INITIALIZE_COMPONENT_DISCRIMINATOR: u64 = 2179155133888827172;
 pub const DESTROY_COMPONENT_DISCRIMINATOR: u64 = 5321952129328727336;
 pub const APPLY_DISCRIMINATOR: u64 = 16258613031726085112;
 pub const APPLY_WITH_SESSION_DISCRIMINATOR: u64 = 7459768094276011477;
+pub const DESTROY_ENTITY_DISCRIMINATOR: u64 = 13999856986449963202;
+pub const ADD_ENTITIES_DISCRIMINATOR: u64 = 15041801809845431365;
+pub const CREATE_ARCHETYPE_DISCRIMINATOR: u64 = 7869174458665086821;
+pub const SPAWN_ARCHETYPE_DISCRIMINATOR: u64 = 8756670823819980324;
+pub const SET_ENTITY_TAGS_DISCRIMINATOR: u64 = 4111635325171018540;
+pub const SET_ENTITY_METADATA_DISCRIMINATOR: u64 = 555219976387849986;
+pub const SET_PARENT_DISCRIMINATOR: u64 = 14109007412529930082;
+pub const CLEAR_PARENT_DISCRIMINATOR: u64 = 3457098379355608738;
+pub const REAP_ENTITY_DISCRIMINATOR: u64 = 13526509800957897780;
+pub const INITIALIZE_FREE_LIST_DISCRIMINATOR: u64 = 18092898886181605671;
+pub const CLONE_ENTITY_DISCRIMINATOR: u64 = 10680482916550098170;
+pub const ADD_ENTITY_WITH_NONCE_DISCRIMINATOR: u64 = 17109459189520781945;
+pub const SET_MIGRATION_POLICY_DISCRIMINATOR: u64 = 9424510969450424708;
+pub const MIGRATE_ENTITY_DISCRIMINATOR: u64 = 2389602881700037186;
+pub const INITIALIZE_COMPRESSED_STORE_DISCRIMINATOR: u64 = 5392501820011781635;
+pub const ADD_COMPRESSED_ENTITY_DISCRIMINATOR: u64 = 7885412520338938125;
+pub const UPDATE_COMPRESSED_ENTITY_DISCRIMINATOR: u64 = 1983871256794658167;
+pub const APPLY_COMPRESSED_DISCRIMINATOR: u64 = 8549952164342276406;
+pub const RESIZE_COMPONENT_DISCRIMINATOR: u64 = 13394555739360450171;
+pub const APPLY_V2_DISCRIMINATOR: u64 = 5305920777779498772;
+pub const APPLY_SYSTEMS_DISCRIMINATOR: u64 = 1619278398601097695;
+pub const APPLY_BATCH_DISCRIMINATOR: u64 = 9215316826927257125;
+pub const UPGRADE_ENTITY_DISCRIMINATOR: u64 = 3779570871900831409;
+pub const INITIALIZE_OUTPUT_BUFFER_DISCRIMINATOR: u64 = 16046712388744136333;
 
 #[repr(u64)]
 pub enum WorldInstruction {
@@ -58,6 +154,30 @@ pub enum WorldInstruction {
     DestroyComponent = DESTROY_COMPONENT_DISCRIMINATOR,
     Apply = APPLY_DISCRIMINATOR,
     ApplyWithSession = APPLY_WITH_SESSION_DISCRIMINATOR,
+    DestroyEntity = DESTROY_ENTITY_DISCRIMINATOR,
+    AddEntities = ADD_ENTITIES_DISCRIMINATOR,
+    CreateArchetype = CREATE_ARCHETYPE_DISCRIMINATOR,
+    SpawnArchetype = SPAWN_ARCHETYPE_DISCRIMINATOR,
+    SetEntityTags = SET_ENTITY_TAGS_DISCRIMINATOR,
+    SetEntityMetadata = SET_ENTITY_METADATA_DISCRIMINATOR,
+    SetParent = SET_PARENT_DISCRIMINATOR,
+    ClearParent = CLEAR_PARENT_DISCRIMINATOR,
+    ReapEntity = REAP_ENTITY_DISCRIMINATOR,
+    InitializeFreeList = INITIALIZE_FREE_LIST_DISCRIMINATOR,
+    CloneEntity = CLONE_ENTITY_DISCRIMINATOR,
+    AddEntityWithNonce = ADD_ENTITY_WITH_NONCE_DISCRIMINATOR,
+    SetMigrationPolicy = SET_MIGRATION_POLICY_DISCRIMINATOR,
+    MigrateEntity = MIGRATE_ENTITY_DISCRIMINATOR,
+    InitializeCompressedStore = INITIALIZE_COMPRESSED_STORE_DISCRIMINATOR,
+    AddCompressedEntity = ADD_COMPRESSED_ENTITY_DISCRIMINATOR,
+    UpdateCompressedEntity = UPDATE_COMPRESSED_ENTITY_DISCRIMINATOR,
+    ApplyCompressed = APPLY_COMPRESSED_DISCRIMINATOR,
+    ResizeComponent = RESIZE_COMPONENT_DISCRIMINATOR,
+    ApplyV2 = APPLY_V2_DISCRIMINATOR,
+    ApplySystems = APPLY_SYSTEMS_DISCRIMINATOR,
+    ApplyBatch = APPLY_BATCH_DISCRIMINATOR,
+    UpgradeEntity = UPGRADE_ENTITY_DISCRIMINATOR,
+    InitializeOutputBuffer = INITIALIZE_OUTPUT_BUFFER_DISCRIMINATOR,
 }
 
 impl TryFrom<u64> for WorldInstruction {
@@ -76,6 +196,32 @@ impl TryFrom<u64> for WorldInstruction {
             DESTROY_COMPONENT_DISCRIMINATOR => Ok(WorldInstruction::DestroyComponent),
             APPLY_DISCRIMINATOR => Ok(WorldInstruction::Apply),
             APPLY_WITH_SESSION_DISCRIMINATOR => Ok(WorldInstruction::ApplyWithSession),
+            DESTROY_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::DestroyEntity),
+            ADD_ENTITIES_DISCRIMINATOR => Ok(WorldInstruction::AddEntities),
+            CREATE_ARCHETYPE_DISCRIMINATOR => Ok(WorldInstruction::CreateArchetype),
+            SPAWN_ARCHETYPE_DISCRIMINATOR => Ok(WorldInstruction::SpawnArchetype),
+            SET_ENTITY_TAGS_DISCRIMINATOR => Ok(WorldInstruction::SetEntityTags),
+            SET_ENTITY_METADATA_DISCRIMINATOR => Ok(WorldInstruction::SetEntityMetadata),
+            SET_PARENT_DISCRIMINATOR => Ok(WorldInstruction::SetParent),
+            CLEAR_PARENT_DISCRIMINATOR => Ok(WorldInstruction::ClearParent),
+            REAP_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::ReapEntity),
+            INITIALIZE_FREE_LIST_DISCRIMINATOR => Ok(WorldInstruction::InitializeFreeList),
+            CLONE_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::CloneEntity),
+            ADD_ENTITY_WITH_NONCE_DISCRIMINATOR => Ok(WorldInstruction::AddEntityWithNonce),
+            SET_MIGRATION_POLICY_DISCRIMINATOR => Ok(WorldInstruction::SetMigrationPolicy),
+            MIGRATE_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::MigrateEntity),
+            INITIALIZE_COMPRESSED_STORE_DISCRIMINATOR => {
+                Ok(WorldInstruction::InitializeCompressedStore)
+            }
+            ADD_COMPRESSED_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::AddCompressedEntity),
+            UPDATE_COMPRESSED_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::UpdateCompressedEntity),
+            APPLY_COMPRESSED_DISCRIMINATOR => Ok(WorldInstruction::ApplyCompressed),
+            RESIZE_COMPONENT_DISCRIMINATOR => Ok(WorldInstruction::ResizeComponent),
+            APPLY_V2_DISCRIMINATOR => Ok(WorldInstruction::ApplyV2),
+            APPLY_SYSTEMS_DISCRIMINATOR => Ok(WorldInstruction::ApplySystems),
+            APPLY_BATCH_DISCRIMINATOR => Ok(WorldInstruction::ApplyBatch),
+            UPGRADE_ENTITY_DISCRIMINATOR => Ok(WorldInstruction::UpgradeEntity),
+            INITIALIZE_OUTPUT_BUFFER_DISCRIMINATOR => Ok(WorldInstruction::InitializeOutputBuffer),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
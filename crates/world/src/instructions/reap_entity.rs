@@ -0,0 +1,150 @@
+use crate::{
+    consts::REAP_BOUNTY_BPS,
+    error::WorldError,
+    instructions::{recycle_entity_id, release_entity, ChildPolicy},
+    state::entity::Entity,
+    utils::close_account,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+/// Permissionlessly closes an expired entity without components. The caller is
+/// paid a bounty out of the entity's rent, the rest goes back to the owner.
+/// Children are orphaned and a child entity is detached from its parent, which
+/// then leads `remaining`.
+pub fn reap_entity(accounts: &[AccountInfo]) -> ProgramResult {
+    let [caller, owner, entity_acct, instruction_sysvar_account, system_program, remaining @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !caller.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let entity = Entity::from_account_info_mut(entity_acct)?;
+
+    if &entity.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    assert_reapable(entity, Clock::get()?.slot)?;
+
+    let remaining = detach_from_parent(entity, entity_acct, remaining)?;
+
+    let free_list = release_entity(
+        entity,
+        entity_acct,
+        owner,
+        caller,
+        instruction_sysvar_account,
+        system_program,
+        remaining,
+        ChildPolicy::Orphan,
+    )?;
+
+    recycle_entity_id(entity, free_list, caller)?;
+
+    let lamports = entity_acct.lamports();
+    let bounty = reap_bounty(lamports)?;
+
+    unsafe {
+        *entity_acct.borrow_mut_lamports_unchecked() = lamports
+            .checked_sub(bounty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *caller.borrow_mut_lamports_unchecked() = caller
+            .lamports()
+            .checked_add(bounty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    close_account(entity_acct, owner)
+}
+
+/// Detaches `entity` from its parent, expected as the first of `remaining`,
+/// and returns the accounts left for `release_entity`.
+fn detach_from_parent<'a>(
+    entity: &mut Entity,
+    entity_acct: &AccountInfo,
+    remaining: &'a [AccountInfo],
+) -> Result<&'a [AccountInfo], ProgramError> {
+    if !entity.has_parent() {
+        return Ok(remaining);
+    }
+
+    let [parent_acct, remaining @ ..] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if &entity.parent != parent_acct.key() || parent_acct.key() == entity_acct.key() {
+        return Err(WorldError::InvalidParent.into());
+    }
+
+    Entity::from_account_info_mut(parent_acct)?.remove_child()?;
+    entity.parent = Pubkey::default();
+
+    Ok(remaining)
+}
+
+/// Checks that `entity` is expired and has no components left. Component
+/// programs only let the entity's authority destroy components, which the
+/// permissionless caller isn't, so they have to be destroyed beforehand.
+fn assert_reapable(entity: &Entity, slot: u64) -> ProgramResult {
+    if !entity.is_expired(slot) {
+        return Err(WorldError::EntityNotExpired.into());
+    }
+
+    if entity.components != 0 {
+        return Err(WorldError::EntityHasComponents.into());
+    }
+
+    Ok(())
+}
+
+/// Share of `lamports` paid to the caller of `reap_entity`.
+fn reap_bounty(lamports: u64) -> Result<u64, ProgramError> {
+    u64::try_from(u128::from(lamports) * u128::from(REAP_BOUNTY_BPS) / 10_000)
+        .map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounty_is_share_of_rent() {
+        assert_eq!(reap_bounty(0).unwrap(), 0);
+        assert_eq!(reap_bounty(10_000).unwrap(), REAP_BOUNTY_BPS);
+        assert_eq!(reap_bounty(1_461_600).unwrap(), 73_080);
+        assert_eq!(reap_bounty(19).unwrap(), 0);
+    }
+
+    #[test]
+    fn reaps_expired_entities_without_components() {
+        let mut entity: Entity = unsafe { core::mem::zeroed() };
+        entity.expiry_slot = 10;
+
+        assert!(assert_reapable(&entity, 9).is_err());
+        assert!(assert_reapable(&entity, 10).is_ok());
+
+        entity.components = 1;
+        assert_eq!(
+            assert_reapable(&entity, 10),
+            Err(WorldError::EntityHasComponents.into())
+        );
+    }
+
+    #[test]
+    fn bounty_does_not_overflow() {
+        assert_eq!(
+            reap_bounty(u64::MAX).unwrap(),
+            (u128::from(u64::MAX) * u128::from(REAP_BOUNTY_BPS) / 10_000) as u64
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    error::WorldError,
+    state::{entity::Entity, world::WorldRef},
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Asks the component program to realloc a component of `entity` to the size
+/// in `data`, with `payer` covering the rent difference either way.
+pub fn resize_component(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, authority, entity_acct, world_acct, component_program, component, instruction_sysvar_account, system_program, system @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let size: [u8; 8] = data
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let entity = Entity::from_account_info(entity_acct)?;
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    entity.assert_authority(authority, &world, system.first())?;
+
+    if !entity.has_components(
+        unsafe { entity_acct.borrow_data_unchecked() },
+        core::slice::from_ref(component_program.key()),
+    )? {
+        return Err(WorldError::ComponentNotFound.into());
+    }
+
+    if !component.is_owned_by(component_program.key()) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Entity::assert_component_pda(entity_acct.key(), component_program.key(), component.key())?;
+
+    hermes_cpi_interface::component::Resize {
+        authority,
+        payer,
+        entity: entity_acct,
+        component,
+        instruction_sysvar_account,
+        system_program,
+        component_program: component_program.key(),
+        size: u64::from_le_bytes(size),
+    }
+    .invoke()
+}
@@ -0,0 +1,89 @@
+use crate::{
+    error::WorldError,
+    state::{
+        entity::{Entity, MetadataEntry, MetadataKey, MAX_METADATA_LEN, METADATA_KEY_LEN},
+        world::WorldRef,
+    },
+    utils::resize_account,
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Inserts, replaces or (with an empty value) removes an inline metadata entry.
+/// Growing the entity is paid by `payer`, shrinking it refunds the entity owner.
+pub fn set_entity_metadata(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, owner, authority, entity_acct, world_acct, _system_program, system @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if data.len() < MetadataEntry::size(0) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (key, rest) = data.split_at(METADATA_KEY_LEN);
+    let (value_len, value) = rest.split_at(core::mem::size_of::<u16>());
+
+    if u16::from_le_bytes([value_len[0], value_len[1]]) as usize != value.len() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let key = unsafe { &*(key.as_ptr() as *const MetadataKey) };
+
+    let entity = Entity::from_account_info_mut(entity_acct)?;
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    entity.assert_authority(authority, &world, system.first())?;
+
+    if &entity.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let metadata_len = entity.metadata_len as usize;
+
+    let (offset, old_len) = match entity
+        .metadata(unsafe { entity_acct.borrow_data_unchecked() })?
+        .get(key)?
+    {
+        Some(entry) => (entry.offset, entry.len()),
+        None => (metadata_len, 0),
+    };
+
+    let new_len = if value.is_empty() {
+        0
+    } else {
+        MetadataEntry::size(value.len())
+    };
+
+    let new_metadata_len = metadata_len - old_len + new_len;
+
+    if new_metadata_len > MAX_METADATA_LEN {
+        return Err(WorldError::MetadataTooLarge.into());
+    }
+
+    let old_size = entity.size();
+    let new_size = entity.metadata_offset() + new_metadata_len;
+    let start = entity.metadata_offset() + offset;
+
+    if new_size > old_size {
+        resize_account(entity_acct, payer, new_size)?;
+    }
+
+    let bytes = unsafe { entity_acct.borrow_mut_data_unchecked() };
+
+    bytes.copy_within(start + old_len..old_size, start + new_len);
+
+    if new_len > 0 {
+        let entry = &mut bytes[start..start + new_len];
+        entry[..METADATA_KEY_LEN].copy_from_slice(key);
+        entry[METADATA_KEY_LEN..METADATA_KEY_LEN + 2].copy_from_slice(value_len);
+        entry[METADATA_KEY_LEN + 2..].copy_from_slice(value);
+    }
+
+    if new_size < old_size {
+        resize_account(entity_acct, owner, new_size)?;
+    }
+
+    entity.metadata_len = new_metadata_len as u32;
+
+    Ok(())
+}
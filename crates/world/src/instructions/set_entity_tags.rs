@@ -0,0 +1,21 @@
+use crate::state::{entity::Entity, world::WorldRef};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+pub fn set_entity_tags(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, entity_acct, world_acct, system @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let tags: [u8; 8] = data
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let entity = Entity::from_account_info_mut(entity_acct)?;
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    entity.assert_authority(authority, &world, system.first())?;
+
+    entity.tags = u64::from_le_bytes(tags);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::{
+    error::WorldError,
+    state::{
+        migration_policy::MigrationPolicy,
+        transmutable::{Transmutable, TransmutableMut},
+        world::WorldRef,
+    },
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+
+pub fn set_migration_policy(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, policy_acct, world_acct, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let [flags] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    if !authority.is_signer() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    if !world.authorities.contains(authority.key()) {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if policy_acct.data_is_empty() {
+        let world_id = world.metadata.id.to_be_bytes();
+
+        let (_, bump) = MigrationPolicy::pda(&world_id);
+
+        CreateAccount {
+            from: authority,
+            to: policy_acct,
+            lamports: Rent::get()?.minimum_balance(MigrationPolicy::LEN),
+            space: MigrationPolicy::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[MigrationPolicy::signer(&world_id, &[bump])
+            .as_slice()
+            .into()])?;
+
+        unsafe { MigrationPolicy::load_mut_unchecked(policy_acct.borrow_mut_data_unchecked())? }
+            .init(world.metadata.id)?;
+    }
+
+    let policy = MigrationPolicy::from_account_info_mut(policy_acct)?;
+
+    if policy.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    policy.set_flags(*flags)
+}
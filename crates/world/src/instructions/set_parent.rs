@@ -0,0 +1,54 @@
+use crate::{error::WorldError, state::entity::Entity};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+pub fn set_parent(accounts: &[AccountInfo]) -> ProgramResult {
+    let [owner, child_acct, parent_acct, ancestors @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if child_acct.key() == parent_acct.key() {
+        return Err(WorldError::InvalidParent.into());
+    }
+
+    let child = Entity::from_account_info_mut(child_acct)?;
+    let parent = Entity::from_account_info_mut(parent_acct)?;
+
+    if &child.owner != owner.key() || &parent.owner != owner.key() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if child.world != parent.world {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if child.has_parent() {
+        return Err(WorldError::EntityHasParent.into());
+    }
+
+    // walk the parent's ancestors up to the root so the child cannot become its own ancestor
+    let mut ancestors = ancestors.iter();
+    let mut next = &parent.parent;
+
+    while next != &Pubkey::default() {
+        if next == child_acct.key() {
+            return Err(WorldError::InvalidParent.into());
+        }
+
+        let ancestor_acct = ancestors.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        if ancestor_acct.key() != next {
+            return Err(WorldError::InvalidParent.into());
+        }
+
+        next = &Entity::from_account_info(ancestor_acct)?.parent;
+    }
+
+    child.parent = *parent_acct.key();
+    parent.add_child()
+}
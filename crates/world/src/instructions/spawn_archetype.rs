@@ -0,0 +1,87 @@
+use crate::{
+    error::WorldError,
+    instructions::create_entity,
+    state::{
+        archetype::ArchetypeRef,
+        entity::{Entity, EntityName},
+        world::WorldMut,
+    },
+};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+pub fn spawn_archetype(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [payer, entity_acct, world_acct, archetype_acct, authority, instruction_sysvar_account, system_program, components @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() && authority.key() != &crate::ID {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    let archetype = ArchetypeRef::from_account_info(archetype_acct)?;
+
+    if archetype.metadata.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    if components.len() as u64 != archetype.metadata.components * 2 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let (name, rest) = EntityName::parse(data)?;
+    let expiry_slot = Entity::parse_expiry_slot(rest)?;
+
+    let entity = create_entity(
+        payer,
+        entity_acct,
+        &mut world,
+        name.as_ref(),
+        expiry_slot,
+        None,
+    )?;
+
+    for (pair, archetype_component) in components.chunks_exact(2).zip(archetype.components()) {
+        let [component_program, component] = pair else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let archetype_component = archetype_component?;
+
+        if component_program.key() != archetype_component.component_program {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if archetype_component.has_default_data() {
+            hermes_cpi_interface::component::InitializeWithData {
+                payer,
+                authority,
+                component_program: component_program.key(),
+                data: component,
+                entity: entity_acct,
+                instruction_sysvar_account,
+                system_program,
+                instruction_data: archetype_component.data,
+            }
+            .invoke()?;
+        } else {
+            hermes_cpi_interface::component::Initialize {
+                payer,
+                authority,
+                component_program: component_program.key(),
+                data: component,
+                entity: entity_acct,
+                instruction_sysvar_account,
+                system_program,
+            }
+            .invoke()?;
+        }
+
+        entity.add_component(entity_acct, payer, component_program.key())?;
+    }
+
+    Ok(())
+}
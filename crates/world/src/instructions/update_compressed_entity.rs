@@ -0,0 +1,71 @@
+use crate::{
+    error::WorldError,
+    state::{
+        compressed::{CompressedEntity, CompressedStore, COMPRESSED_TREE_DEPTH},
+        world::WorldRef,
+    },
+};
+use hermes_merkle::Node;
+use pinocchio::{
+    account_info::AccountInfo, log::sol_log_data, program_error::ProgramError, ProgramResult,
+};
+
+/// Replaces the tags and data hash of a compressed entity. `data` holds the
+/// root the proof was made against, the current entity record, the new tags
+/// and data hash, and the proof. The owner or an approved system's authority
+/// must sign, as for `set_entity_tags`.
+pub fn update_compressed_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, store_acct, world_acct, system @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    const NODE_LEN: usize = core::mem::size_of::<Node>();
+
+    if data.len() < NODE_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (root, rest) = data.split_at(NODE_LEN);
+    let root = unsafe { &*(root.as_ptr() as *const Node) };
+
+    let (mut entity, rest) = CompressedEntity::parse(rest)?;
+
+    if rest.len() != core::mem::size_of::<u64>() + NODE_LEN + COMPRESSED_TREE_DEPTH * NODE_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (tags, rest) = rest.split_at(core::mem::size_of::<u64>());
+    let (data_hash, proof) = rest.split_at(NODE_LEN);
+    let proof = unsafe {
+        core::slice::from_raw_parts(proof.as_ptr() as *const Node, COMPRESSED_TREE_DEPTH)
+    };
+
+    let (store, tree) = CompressedStore::from_account_info_mut(store_acct)?;
+    let world = WorldRef::from_account_info(world_acct)?;
+
+    if store.world != world.metadata.id {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    world.assert_entity_authority(&entity.owner, authority, system.first())?;
+
+    let previous_leaf = entity.leaf(store.world);
+
+    entity.tags = u64::from_le_bytes(unsafe { (tags.as_ptr() as *const [u8; 8]).read() });
+    entity.data_hash = unsafe { (data_hash.as_ptr() as *const Node).read() };
+
+    let index = u32::try_from(entity.id).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    tree.set_leaf(
+        root,
+        &previous_leaf,
+        &entity.leaf(store.world),
+        proof,
+        index,
+    )
+    .map_err(WorldError::from)?;
+
+    sol_log_data(&[&store.world.to_le_bytes(), &entity.to_bytes()]);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::{
+    error::WorldError,
+    state::{
+        account::AnchorAccount,
+        entity::{Entity, EntityKind, EntityName},
+        transmutable::{Transmutable, TransmutableMut},
+        world::WorldMut,
+    },
+    utils::{assert_program_account_and_discriminator, resize_account},
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{Pubkey, PUBKEY_BYTES},
+    ProgramResult,
+};
+
+/// Reallocs an entity created with the legacy `[discriminator][id]` layout to
+/// the current one, so its components can be managed again. Legacy entities
+/// have no owner, so a world authority assigns one. The data is `[owner]`
+/// followed by the Borsh `Option<Vec<u8>>` name the entity was spawned with.
+pub fn upgrade_entity(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, payer, entity_acct, world_acct, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    let mut world = WorldMut::from_account_info(world_acct)?;
+
+    if !world.authorities()?.contains(authority.key()) {
+        return Err(WorldError::InvalidAuthority.into());
+    }
+
+    if data.len() < PUBKEY_BYTES {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (owner, rest) = data.split_at(PUBKEY_BYTES);
+    let owner: &Pubkey = unsafe { &*(owner.as_ptr() as *const Pubkey) };
+    let (name, rest) = EntityName::parse(rest)?;
+
+    if !rest.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    assert_program_account_and_discriminator(entity_acct, &Entity::DISCRIMINATOR)?;
+
+    if entity_acct.data_len() != Entity::LEGACY_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let id = u64::from_le_bytes(unsafe {
+        (entity_acct.borrow_data_unchecked()[8..16].as_ptr() as *const [u8; 8]).read()
+    });
+
+    let world_id = world.metadata.id.to_be_bytes();
+
+    let (expected, kind) = match &name {
+        Some(name) => (
+            Entity::pda(&world_id, name.id_seed(), name.as_seed()).0,
+            EntityKind::Named,
+        ),
+        None => (Entity::find(world.metadata.id, id).0, EntityKind::Counter),
+    };
+
+    if &expected != entity_acct.key() {
+        return Err(WorldError::WorldAccountMismatch.into());
+    }
+
+    resize_account(entity_acct, payer, Entity::LEN)?;
+
+    let entity = unsafe { Entity::load_mut_unchecked(entity_acct.borrow_mut_data_unchecked())? };
+    entity.init(world.metadata.id, id, kind, owner)
+}
@@ -1,23 +1,31 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![allow(unexpected_cfgs)]
 
+mod apply;
 mod consts;
-mod error;
+pub mod error;
 mod instructions;
-mod state;
+pub mod state;
+mod system_output;
+#[cfg(test)]
+mod test_utils;
 mod utils;
 
 use consts::DISCRIMATOR_LENGTH;
 use instructions::*;
 use pinocchio::{
-    account_info::AccountInfo, entrypoint, program_error::ProgramError, pubkey::Pubkey,
-    ProgramResult,
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
 
+#[cfg(not(test))]
+use pinocchio::entrypoint;
+
 pinocchio_pubkey::declare_id!("WorLD15A7CrDwLcLy4fRqtaTb9fbd8o8iqiEMUDse2n");
 
+#[cfg(not(test))]
 pinocchio::nostd_panic_handler!();
 
+#[cfg(not(test))]
 pinocchio::entrypoint!(process_instruction);
 
 pub fn process_instruction(
@@ -38,12 +46,36 @@ pub fn process_instruction(
         WorldInstruction::InitializeNewWorld => initialize_new_world(accounts),
         WorldInstruction::AddAuthority => add_authority(accounts, data),
         WorldInstruction::RemoveAuthority => remove_authority(accounts, data),
-        WorldInstruction::InitilizeComponent => initialize_component(accounts),
+        WorldInstruction::InitilizeComponent => initialize_component(accounts, data),
         WorldInstruction::DestroyComponent => destroy_component(accounts),
         WorldInstruction::ApproveSystem => approve_system(accounts),
         WorldInstruction::RemoveSystem => remove_system(accounts),
         WorldInstruction::Apply => apply_system(accounts, data),
         WorldInstruction::ApplyWithSession => apply_system_session(accounts, data),
         WorldInstruction::AddEntity => add_entity(accounts, data),
+        WorldInstruction::DestroyEntity => destroy_entity(accounts, data),
+        WorldInstruction::AddEntities => add_entities(accounts, data),
+        WorldInstruction::CreateArchetype => create_archetype(accounts, data),
+        WorldInstruction::SpawnArchetype => spawn_archetype(accounts, data),
+        WorldInstruction::SetEntityTags => set_entity_tags(accounts, data),
+        WorldInstruction::SetEntityMetadata => set_entity_metadata(accounts, data),
+        WorldInstruction::SetParent => set_parent(accounts),
+        WorldInstruction::ClearParent => clear_parent(accounts),
+        WorldInstruction::ReapEntity => reap_entity(accounts),
+        WorldInstruction::InitializeFreeList => initialize_free_list(accounts),
+        WorldInstruction::CloneEntity => clone_entity(accounts, data),
+        WorldInstruction::AddEntityWithNonce => add_entity_with_nonce(accounts, data),
+        WorldInstruction::SetMigrationPolicy => set_migration_policy(accounts, data),
+        WorldInstruction::MigrateEntity => migrate_entity(accounts, data),
+        WorldInstruction::InitializeCompressedStore => initialize_compressed_store(accounts),
+        WorldInstruction::AddCompressedEntity => add_compressed_entity(accounts, data),
+        WorldInstruction::UpdateCompressedEntity => update_compressed_entity(accounts, data),
+        WorldInstruction::ApplyCompressed => apply_compressed(accounts, data),
+        WorldInstruction::ResizeComponent => resize_component(accounts, data),
+        WorldInstruction::ApplyV2 => apply_v2(accounts, data),
+        WorldInstruction::ApplySystems => apply_systems(accounts, data),
+        WorldInstruction::ApplyBatch => apply_batch(accounts, data),
+        WorldInstruction::UpgradeEntity => upgrade_entity(accounts, data),
+        WorldInstruction::InitializeOutputBuffer => initialize_output_buffer(accounts),
     }
 }
@@ -0,0 +1,229 @@
+use super::{
+    account::AnchorAccount,
+    transmutable::{Transmutable, TransmutableMut},
+};
+use crate::consts::MAX_UPDATE_DATA_LEN;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+pub struct Archetype;
+
+impl Archetype {
+    pub const DISCRIMINATOR: [u8; 8] = [248, 90, 110, 108, 119, 217, 182, 28];
+
+    fn archetype_seed() -> &'static [u8] {
+        b"archetype"
+    }
+
+    pub fn pda(world_id: &[u8; 8], archetype_id: &[u8; 8]) -> (Pubkey, u8) {
+        find_program_address(
+            &[Self::archetype_seed(), world_id, archetype_id],
+            &crate::ID,
+        )
+    }
+
+    pub fn signer<'a>(
+        world_id: &'a [u8; 8],
+        archetype_id: &'a [u8; 8],
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 4] {
+        [
+            Self::archetype_seed().as_ref().into(),
+            world_id.as_ref().into(),
+            archetype_id.as_ref().into(),
+            bump.as_ref().into(),
+        ]
+    }
+
+    pub fn size(components: &[u8]) -> usize {
+        ArchetypeMetadata::LEN + components.len()
+    }
+}
+
+#[repr(C)]
+pub struct ArchetypeMetadata {
+    pub discriminator: [u8; 8],
+    pub world: u64,
+    pub id: u64,
+    pub components: u64,
+}
+
+impl TransmutableMut for ArchetypeMetadata {}
+
+impl Transmutable for ArchetypeMetadata {
+    const LEN: usize = core::mem::size_of::<ArchetypeMetadata>();
+}
+
+impl ArchetypeMetadata {
+    pub fn init(&mut self, world: u64, id: u64, components: u64) -> Result<(), ProgramError> {
+        self.discriminator = Archetype::DISCRIMINATOR;
+        self.world = world;
+        self.id = id;
+        self.components = components;
+        Ok(())
+    }
+}
+
+/// Component of an archetype: the component program and the Borsh `Vec<u8>` of
+/// default data the component is seeded with.
+pub struct ArchetypeComponent<'a> {
+    pub component_program: &'a Pubkey,
+    pub data: &'a [u8],
+}
+
+impl ArchetypeComponent<'_> {
+    pub fn has_default_data(&self) -> bool {
+        self.data.len() > core::mem::size_of::<u32>()
+    }
+}
+
+/// Iterates over `[component_program][u32 len][data]` entries.
+pub struct ArchetypeComponents<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ArchetypeComponents<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Checks that `data` holds exactly `count` well-formed entries whose
+    /// default data fits in `MAX_UPDATE_DATA_LEN`.
+    pub fn validate(data: &[u8], count: u64) -> Result<(), ProgramError> {
+        let mut components = ArchetypeComponents::new(data);
+
+        for _ in 0..count {
+            let component = components
+                .next()
+                .ok_or(ProgramError::InvalidInstructionData)??;
+
+            if component.data.len() > MAX_UPDATE_DATA_LEN {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        if !components.data.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for ArchetypeComponents<'a> {
+    type Item = Result<ArchetypeComponent<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        const HEADER_LEN: usize = core::mem::size_of::<Pubkey>() + core::mem::size_of::<u32>();
+
+        if self.data.len() < HEADER_LEN {
+            return Some(Err(ProgramError::InvalidAccountData));
+        }
+
+        let (component_program, rest) = self.data.split_at(core::mem::size_of::<Pubkey>());
+
+        let len = u32::from_le_bytes(unsafe { (rest.as_ptr() as *const [u8; 4]).read() }) as usize;
+
+        let size = core::mem::size_of::<u32>() + len;
+
+        if rest.len() < size {
+            return Some(Err(ProgramError::InvalidAccountData));
+        }
+
+        let (data, rest) = rest.split_at(size);
+
+        self.data = rest;
+
+        Some(Ok(ArchetypeComponent {
+            component_program: unsafe { &*(component_program.as_ptr() as *const Pubkey) },
+            data,
+        }))
+    }
+}
+
+pub struct ArchetypeRef<'a> {
+    pub metadata: &'a ArchetypeMetadata,
+    pub components: &'a [u8],
+}
+
+impl<'a> ArchetypeRef<'a> {
+    pub fn from_account_info(account_info: &'a AccountInfo) -> Result<Self, ProgramError> {
+        let data = Self::from_bytes(unsafe { account_info.borrow_data_unchecked() })?;
+        data.assert_account(account_info)?;
+        Ok(data)
+    }
+
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, ProgramError> {
+        if bytes.len() < ArchetypeMetadata::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (metadata_bytes, components) = bytes.split_at(ArchetypeMetadata::LEN);
+        let metadata = unsafe { ArchetypeMetadata::load_unchecked(metadata_bytes)? };
+
+        Ok(Self {
+            metadata,
+            components,
+        })
+    }
+
+    pub fn components(&self) -> ArchetypeComponents<'a> {
+        ArchetypeComponents::new(self.components)
+    }
+}
+
+impl AnchorAccount for ArchetypeRef<'_> {
+    const DISCRIMINATOR: [u8; 8] = Archetype::DISCRIMINATOR;
+
+    fn discriminator(&self) -> [u8; 8] {
+        self.metadata.discriminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(program: u8, data: &[u8]) -> Vec<u8> {
+        let mut entry = vec![program; 32];
+        entry.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        entry.extend_from_slice(data);
+        entry
+    }
+
+    #[test]
+    fn validate_accepts_exact_entries() {
+        let mut data = entry(1, &[]);
+        data.extend(entry(2, &[7; MAX_UPDATE_DATA_LEN - 4]));
+
+        ArchetypeComponents::validate(&data, 2).unwrap();
+
+        let components = ArchetypeComponents::new(&data)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(!components[0].has_default_data());
+        assert!(components[1].has_default_data());
+    }
+
+    #[test]
+    fn validate_rejects_oversized_default_data() {
+        let data = entry(1, &[7; MAX_UPDATE_DATA_LEN - 3]);
+        assert!(ArchetypeComponents::validate(&data, 1).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_count_mismatch_and_truncation() {
+        let data = entry(1, &[7; 4]);
+        assert!(ArchetypeComponents::validate(&data, 2).is_err());
+        assert!(ArchetypeComponents::validate(&data, 0).is_err());
+        assert!(ArchetypeComponents::validate(&data[..data.len() - 1], 1).is_err());
+    }
+}
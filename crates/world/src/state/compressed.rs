@@ -0,0 +1,198 @@
+use super::{
+    account::AnchorAccount,
+    transmutable::{Transmutable, TransmutableMut},
+};
+use hermes_merkle::{hashv, ConcurrentMerkleTree, Node};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+pub const COMPRESSED_TREE_DEPTH: usize = 24;
+
+pub const COMPRESSED_TREE_BUFFER: usize = 64;
+
+pub type CompressedTree = ConcurrentMerkleTree<COMPRESSED_TREE_DEPTH, COMPRESSED_TREE_BUFFER>;
+
+/// Store of entities kept as leaves of a concurrent Merkle tree instead of one
+/// account each. The tree follows the header; the account is too large to be
+/// created by the program, so clients create it and `initialize_compressed_store`
+/// claims it. Systems are applied to compressed entities through
+/// `apply_compressed`, and `update_compressed_entity` replaces a record's tags
+/// and data hash directly.
+#[repr(C)]
+pub struct CompressedStore {
+    pub discriminator: [u8; 8],
+    pub world: u64,
+}
+
+impl CompressedStore {
+    pub const SIZE: usize = Self::LEN + core::mem::size_of::<CompressedTree>();
+
+    pub fn init(&mut self, world: u64) -> Result<(), ProgramError> {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.world = world;
+        Ok(())
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info_mut(
+        account_info: &AccountInfo,
+    ) -> Result<(&mut Self, &mut CompressedTree), ProgramError> {
+        let (store, tree) = unsafe { Self::load_unchecked_with_tree(account_info)? };
+        store.assert_account(account_info)?;
+        Ok((store, tree))
+    }
+
+    /// Lays the header and the tree over the account data without checking
+    /// the discriminator.
+    ///
+    /// # Safety
+    ///
+    /// The account data must not be borrowed elsewhere.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn load_unchecked_with_tree(
+        account_info: &AccountInfo,
+    ) -> Result<(&mut Self, &mut CompressedTree), ProgramError> {
+        let data = account_info.borrow_mut_data_unchecked();
+
+        if data.len() != Self::SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, tree) = data.split_at_mut(Self::LEN);
+
+        Ok((
+            Self::load_mut_unchecked(header)?,
+            &mut *(tree.as_mut_ptr() as *mut CompressedTree),
+        ))
+    }
+}
+
+impl TransmutableMut for CompressedStore {}
+
+impl Transmutable for CompressedStore {
+    const LEN: usize = core::mem::size_of::<CompressedStore>();
+}
+
+impl AnchorAccount for CompressedStore {
+    const DISCRIMINATOR: [u8; 8] = [88, 14, 136, 136, 43, 97, 68, 45];
+
+    fn discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+}
+
+/// Entity record hashed into a compressed store leaf. `data_hash` commits to
+/// the entity's off-chain component data, as `hash_data` of it for entities
+/// systems are applied to.
+pub struct CompressedEntity {
+    pub id: u64,
+    pub owner: Pubkey,
+    pub tags: u64,
+    pub data_hash: Node,
+}
+
+impl CompressedEntity {
+    pub const LEN: usize = 2 * core::mem::size_of::<u64>()
+        + core::mem::size_of::<Pubkey>()
+        + core::mem::size_of::<Node>();
+
+    /// Parses `[id u64][owner][tags u64][data_hash]`, returning the bytes that follow.
+    pub fn parse(data: &[u8]) -> Result<(Self, &[u8]), ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (entity, rest) = data.split_at(Self::LEN);
+
+        Ok((
+            Self {
+                id: u64::from_le_bytes(field(entity, 0)?),
+                owner: field(entity, 8)?,
+                tags: u64::from_le_bytes(field(entity, 40)?),
+                data_hash: field(entity, 48)?,
+            },
+            rest,
+        ))
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0..8].copy_from_slice(&self.id.to_le_bytes());
+        bytes[8..40].copy_from_slice(&self.owner);
+        bytes[40..48].copy_from_slice(&self.tags.to_le_bytes());
+        bytes[48..80].copy_from_slice(&self.data_hash);
+        bytes
+    }
+
+    pub fn hash_data(data: &[u8]) -> Node {
+        hashv(&[b"entity-data", data])
+    }
+
+    pub fn leaf(&self, world: u64) -> Node {
+        hashv(&[b"entity", &world.to_le_bytes(), &self.to_bytes()])
+    }
+}
+
+fn field<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N], ProgramError> {
+    data.get(offset..offset + N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity() -> CompressedEntity {
+        CompressedEntity {
+            id: 7,
+            owner: [1; 32],
+            tags: 0b101,
+            data_hash: [2; 32],
+        }
+    }
+
+    #[test]
+    fn encodes_record() {
+        let bytes = entity().to_bytes();
+
+        assert_eq!(bytes.len(), CompressedEntity::LEN);
+        assert_eq!(&bytes[0..8], &7u64.to_le_bytes());
+        assert_eq!(&bytes[8..40], &[1; 32]);
+        assert_eq!(&bytes[40..48], &0b101u64.to_le_bytes());
+        assert_eq!(&bytes[48..80], &[2; 32]);
+    }
+
+    #[test]
+    fn leaf_commits_to_world_and_record() {
+        let entity = entity();
+
+        assert_eq!(
+            entity.leaf(3),
+            hashv(&[b"entity", &3u64.to_le_bytes(), &entity.to_bytes()])
+        );
+        assert_ne!(entity.leaf(3), entity.leaf(4));
+
+        let leaf = entity.leaf(3);
+        let retagged = CompressedEntity { tags: 0, ..entity };
+        assert_ne!(retagged.leaf(3), leaf);
+    }
+
+    #[test]
+    fn parse_round_trips() {
+        let mut data = entity().to_bytes().to_vec();
+        data.extend_from_slice(&[9, 9]);
+
+        let (parsed, rest) = CompressedEntity::parse(&data).unwrap();
+
+        assert_eq!(parsed.to_bytes(), entity().to_bytes());
+        assert_eq!(rest, &[9, 9]);
+    }
+
+    #[test]
+    fn parse_rejects_short_record() {
+        let data = entity().to_bytes();
+
+        assert!(CompressedEntity::parse(&data[..CompressedEntity::LEN - 1]).is_err());
+        assert!(CompressedEntity::parse(&[]).is_err());
+    }
+}
@@ -1,81 +1,528 @@
 use super::{
     account::AnchorAccount,
     transmutable::{Transmutable, TransmutableMut},
+    world::WorldRef,
 };
+use crate::{error::WorldError, utils::resize_account};
+use hermes_merkle::hashv;
 use pinocchio::{
+    account_info::AccountInfo,
     instruction::Seed,
     program_error::ProgramError,
-    pubkey::{find_program_address, Pubkey},
+    pubkey::{find_program_address, try_find_program_address, Pubkey, MAX_SEED_LEN},
+    ProgramResult,
 };
 
+/// Fixed entity header. The component index (`components` program ids) and
+/// then the inline metadata entries follow it in the account data.
+///
+/// Entities can be filtered with `getProgramAccounts` memcmp filters at the
+/// `*_OFFSET` constants below, e.g. all entities of a world via `WORLD_OFFSET`
+/// or every entity carrying an exact tag mask via `TAGS_OFFSET`. Single tag
+/// bytes can be matched at `TAGS_OFFSET + n` since the mask is little-endian.
+/// The children of an entity are the entities with its key at `PARENT_OFFSET`.
+/// Entities that never expire have a zero `u64` at `EXPIRY_SLOT_OFFSET`, and
+/// legacy entities still awaiting `upgrade_entity` are the ones of size
+/// `LEGACY_LEN`.
 #[repr(C)]
 pub struct Entity {
     pub discriminator: [u8; 8],
     pub id: u64,
+    pub world: u64,
+    pub owner: Pubkey,
+    /// Parent entity, or the default pubkey for root entities.
+    pub parent: Pubkey,
+    pub tags: u64,
+    /// Length of the component index.
+    pub components: u64,
+    pub children: u64,
+    /// Slot from which the entity can be reaped, or 0 if it never expires.
+    pub expiry_slot: u64,
+    pub metadata_len: u32,
+    pub kind: u8,
+    pub version: u8,
 }
 
 impl Entity {
+    pub const ID_OFFSET: usize = core::mem::offset_of!(Entity, id);
+    pub const WORLD_OFFSET: usize = core::mem::offset_of!(Entity, world);
+    pub const OWNER_OFFSET: usize = core::mem::offset_of!(Entity, owner);
+    pub const PARENT_OFFSET: usize = core::mem::offset_of!(Entity, parent);
+    pub const TAGS_OFFSET: usize = core::mem::offset_of!(Entity, tags);
+    pub const EXPIRY_SLOT_OFFSET: usize = core::mem::offset_of!(Entity, expiry_slot);
+    pub const KIND_OFFSET: usize = core::mem::offset_of!(Entity, kind);
+    pub const VERSION_OFFSET: usize = core::mem::offset_of!(Entity, version);
+
+    pub const VERSION: u8 = 1;
+
+    /// Size of the `[discriminator][id]` entities created before the header
+    /// grew, which have to go through `upgrade_entity` first.
+    pub const LEGACY_LEN: usize = 16;
+
     pub fn seeds() -> &'static [u8] {
         b"entity".as_ref()
     }
 
-    pub fn init(&mut self, id: u64) -> Result<(), ProgramError> {
+    fn nonce_seeds() -> &'static [u8] {
+        b"entity-nonce".as_ref()
+    }
+
+    pub fn init(
+        &mut self,
+        world: u64,
+        id: u64,
+        kind: EntityKind,
+        owner: &Pubkey,
+    ) -> Result<(), ProgramError> {
         self.discriminator = Self::DISCRIMINATOR;
         self.id = id;
+        self.world = world;
+        self.owner = *owner;
+        self.parent = Pubkey::default();
+        self.tags = 0;
+        self.components = 0;
+        self.children = 0;
+        self.expiry_slot = 0;
+        self.metadata_len = 0;
+        self.kind = kind as u8;
+        self.version = Self::VERSION;
         Ok(())
-    } 
+    }
 
-    pub fn remaining_seeds<'a>(
-        world_entity: &'a [u8],
-        extra_seed: &'a [u8], // Option<u8>: Borsh vec
-    ) -> Result<(&'a [u8], &'a [u8]), ProgramError> {
-        let (is_extra_seeds_bytes, seeds_vec_bytes) = extra_seed
-            .split_first()
-            .ok_or(ProgramError::InvalidInstructionData)?;
-        let is_extra_seeds = match is_extra_seeds_bytes {
-            0 => Ok(false),
-            1 => Ok(true),
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<&Self, ProgramError> {
+        let data = unsafe { account_info.borrow_data_unchecked() };
+        Self::assert_current_layout(data)?;
+        let entity = unsafe { Self::load_unchecked(&data[..Self::LEN])? };
+        entity.assert_account(account_info)?;
+        entity.assert_version()?;
+        Ok(entity)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let data = unsafe { account_info.borrow_mut_data_unchecked() };
+        Self::assert_current_layout(data)?;
+        let entity = unsafe { Self::load_mut_unchecked(&mut data[..Self::LEN])? };
+        entity.assert_account(account_info)?;
+        entity.assert_version()?;
+        Ok(entity)
+    }
+
+    fn assert_current_layout(data: &[u8]) -> ProgramResult {
+        match data.len() {
+            Self::LEGACY_LEN => Err(WorldError::LegacyEntity.into()),
+            len if len < Self::LEN => Err(ProgramError::InvalidAccountData),
+            _ => Ok(()),
+        }
+    }
+
+    fn assert_version(&self) -> ProgramResult {
+        if self.version != Self::VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Checks that `authority` may edit the entity: either its owner, or the
+    /// system authority of a system approved by the entity's world.
+    pub fn assert_authority(
+        &self,
+        authority: &AccountInfo,
+        world: &WorldRef,
+        system: Option<&AccountInfo>,
+    ) -> Result<(), ProgramError> {
+        if self.world != world.metadata.id {
+            return Err(WorldError::WorldAccountMismatch.into());
+        }
+
+        world.assert_entity_authority(&self.owner, authority, system)
+    }
+
+    pub fn metadata_offset(&self) -> usize {
+        Self::LEN + self.components as usize * core::mem::size_of::<Pubkey>()
+    }
+
+    pub fn size(&self) -> usize {
+        self.metadata_offset() + self.metadata_len as usize
+    }
+
+    pub fn metadata<'a>(&self, data: &'a [u8]) -> Result<EntityMetadata<'a>, ProgramError> {
+        let metadata = data
+            .get(self.metadata_offset()..self.size())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(EntityMetadata::new(metadata))
+    }
+
+    /// Programs of the components initialized for this entity, in initialization order.
+    pub fn component_index<'a>(&self, data: &'a [u8]) -> Result<&'a [Pubkey], ProgramError> {
+        let index = data
+            .get(Self::LEN..self.metadata_offset())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Ok(unsafe {
+            core::slice::from_raw_parts(index.as_ptr() as *const Pubkey, self.components as usize)
+        })
+    }
+
+    /// Whether the entity has a component of every program in `required`.
+    pub fn has_components(&self, data: &[u8], required: &[Pubkey]) -> Result<bool, ProgramError> {
+        let index = self.component_index(data)?;
+        Ok(required.iter().all(|program| index.contains(program)))
+    }
+
+    pub fn add_component(
+        &mut self,
+        entity_acct: &AccountInfo,
+        payer: &AccountInfo,
+        component_program: &Pubkey,
+    ) -> ProgramResult {
+        if self
+            .component_index(unsafe { entity_acct.borrow_data_unchecked() })?
+            .contains(component_program)
+        {
+            return Err(WorldError::ComponentAlreadyExists.into());
+        }
+
+        let size = self.size();
+        let offset = self.metadata_offset();
+
+        resize_account(entity_acct, payer, size + core::mem::size_of::<Pubkey>())?;
+
+        let data = unsafe { entity_acct.borrow_mut_data_unchecked() };
+        data.copy_within(offset..size, offset + core::mem::size_of::<Pubkey>());
+        data[offset..offset + core::mem::size_of::<Pubkey>()].copy_from_slice(component_program);
+
+        self.components += 1;
+
+        Ok(())
+    }
+
+    pub fn remove_component(
+        &mut self,
+        entity_acct: &AccountInfo,
+        receiver: &AccountInfo,
+        component_program: &Pubkey,
+    ) -> ProgramResult {
+        let position = self
+            .component_index(unsafe { entity_acct.borrow_data_unchecked() })?
+            .iter()
+            .position(|program| program == component_program)
+            .ok_or(WorldError::ComponentNotFound)?;
+
+        let size = self.size();
+        let offset = Self::LEN + position * core::mem::size_of::<Pubkey>();
+
+        let data = unsafe { entity_acct.borrow_mut_data_unchecked() };
+        data.copy_within(offset + core::mem::size_of::<Pubkey>()..size, offset);
+
+        self.components -= 1;
+
+        resize_account(entity_acct, receiver, size - core::mem::size_of::<Pubkey>())
+    }
+
+    pub fn is_expired(&self, slot: u64) -> bool {
+        self.expiry_slot != 0 && slot >= self.expiry_slot
+    }
+
+    /// Parses the Borsh `Option<u64>` expiry slot that may trail spawn
+    /// instruction data, returning 0 when it is absent.
+    pub fn parse_expiry_slot(data: &[u8]) -> Result<u64, ProgramError> {
+        match data {
+            [] | [0] => Ok(0),
+            [1, slot @ ..] => Ok(u64::from_le_bytes(
+                slot.try_into()
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )),
             _ => Err(ProgramError::InvalidInstructionData),
-        }?;
+        }
+    }
 
-        let seeds: (&[u8], &[u8]) = if is_extra_seeds {
-            let (_, s) = seeds_vec_bytes.split_at(4);
-            ([0; 8].as_slice(), s)
-        } else {
-            (world_entity, &[])
-        };
+    pub fn has_parent(&self) -> bool {
+        self.parent != Pubkey::default()
+    }
 
-        Ok(seeds)
+    pub fn add_child(&mut self) -> Result<(), ProgramError> {
+        self.children = self
+            .children
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
     }
 
-    pub fn pda(
-        world_id: &[u8; 8],
-        world_entity: &[u8; 8],
-        extra_seed: &[u8],
-    ) -> Result<(Pubkey, u8), ProgramError> {
-        let seeds = Self::remaining_seeds(world_entity, extra_seed)?;
+    pub fn remove_child(&mut self) -> Result<(), ProgramError> {
+        self.children = self
+            .children
+            .checked_sub(1)
+            .ok_or(WorldError::InvalidParent)?;
+        Ok(())
+    }
 
-        Ok(find_program_address(
-            &[Entity::seeds(), world_id, seeds.0, seeds.1],
-            &crate::ID,
-        ))
+    pub fn kind(&self) -> Result<EntityKind, ProgramError> {
+        EntityKind::try_from(self.kind)
+    }
+
+    pub fn pda(world_id: &[u8; 8], entity_id: &[u8; 8], name: &[u8]) -> (Pubkey, u8) {
+        find_program_address(&[Entity::seeds(), world_id, entity_id, name], &crate::ID)
+    }
+
+    /// Address of the component of `component_program` attached to `entity`.
+    pub fn component_pda(entity: &Pubkey, component_program: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[entity], component_program)
+    }
+
+    /// Checks that `component` is the component of `component_program`
+    /// attached to `entity`.
+    pub fn assert_component_pda(
+        entity: &Pubkey,
+        component_program: &Pubkey,
+        component: &Pubkey,
+    ) -> ProgramResult {
+        match try_find_program_address(&[entity], component_program) {
+            Some((pda, _)) if &pda == component => Ok(()),
+            _ => Err(ProgramError::InvalidSeeds),
+        }
     }
 
     pub fn signer<'a>(
         world_id: &'a [u8; 8],
-        world_entity: &'a [u8; 8],
-        extra_seed: &'a [u8],
+        entity_id: &'a [u8; 8],
+        name: &'a [u8],
         bump: &'a [u8; 1],
-    ) -> Result<[Seed<'a>; 5], ProgramError> {
-        let seeds = Self::remaining_seeds(world_entity, extra_seed)?;
-
-        Ok([
+    ) -> [Seed<'a>; 5] {
+        [
             Self::seeds().as_ref().into(),
             world_id.as_ref().into(),
-            seeds.0.as_ref().into(),
-            seeds.1.as_ref().into(),
+            entity_id.as_ref().into(),
+            name.into(),
+            bump.as_ref().into(),
+        ]
+    }
+
+    pub fn nonce_pda(world_id: &[u8; 8], creator: &Pubkey, nonce: &[u8; 8]) -> (Pubkey, u8) {
+        find_program_address(
+            &[Entity::nonce_seeds(), world_id, creator, nonce],
+            &crate::ID,
+        )
+    }
+
+    pub fn nonce_signer<'a>(
+        world_id: &'a [u8; 8],
+        creator: &'a Pubkey,
+        nonce: &'a [u8; 8],
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 5] {
+        [
+            Self::nonce_seeds().as_ref().into(),
+            world_id.as_ref().into(),
+            creator.as_ref().into(),
+            nonce.as_ref().into(),
             bump.as_ref().into(),
-        ])
+        ]
+    }
+
+    /// Address of the entity `creator` spawned with `nonce` in world `world_id`.
+    pub fn find_with_nonce(world_id: u64, creator: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+        Self::nonce_pda(&world_id.to_be_bytes(), creator, &nonce.to_be_bytes())
+    }
+
+    /// Address of the counter-based entity `entity_id` in world `world_id`.
+    pub fn find(world_id: u64, entity_id: u64) -> (Pubkey, u8) {
+        Self::pda(&world_id.to_be_bytes(), &entity_id.to_be_bytes(), &[])
+    }
+
+    /// Address of the entity spawned with `name` in world `world_id`.
+    pub fn find_named(world_id: u64, name: &[u8]) -> Result<(Pubkey, u8), ProgramError> {
+        let name = EntityName::new(name)?;
+        Ok(Self::pda(
+            &world_id.to_be_bytes(),
+            name.id_seed(),
+            name.as_seed(),
+        ))
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    Counter,
+    Named,
+    /// Derived from the creator and a client nonce, without writing the world.
+    Nonce,
+}
+
+impl TryFrom<u8> for EntityKind {
+    type Error = ProgramError;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0 => Ok(EntityKind::Counter),
+            1 => Ok(EntityKind::Named),
+            2 => Ok(EntityKind::Nonce),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// Seed used in place of the entity id for named entities.
+pub const NAMED_ENTITY_ID: [u8; 8] = [0; 8];
+
+/// Seed used in place of the entity id for names hashed into their seed, so a
+/// raw name can never derive the same address as the hash of a longer one.
+pub const HASHED_NAME_ENTITY_ID: [u8; 8] = *b"longname";
+
+/// PDA seed for a named entity. Names longer than a single seed are hashed.
+pub struct EntityName {
+    id_seed: &'static [u8; 8],
+    seed: [u8; MAX_SEED_LEN],
+    len: usize,
+}
+
+impl EntityName {
+    pub fn new(name: &[u8]) -> Result<Self, ProgramError> {
+        if name.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if name.len() > MAX_SEED_LEN {
+            return Ok(Self {
+                id_seed: &HASHED_NAME_ENTITY_ID,
+                seed: hashv(&[name]),
+                len: MAX_SEED_LEN,
+            });
+        }
+
+        let mut seed = [0u8; MAX_SEED_LEN];
+        seed[..name.len()].copy_from_slice(name);
+
+        Ok(Self {
+            id_seed: &NAMED_ENTITY_ID,
+            seed,
+            len: name.len(),
+        })
+    }
+
+    /// Parses a Borsh `Option<Vec<u8>>` name, returning it with the bytes that follow.
+    pub fn parse(data: &[u8]) -> Result<(Option<Self>, &[u8]), ProgramError> {
+        let (is_named, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        match is_named {
+            0 => Ok((None, rest)),
+            1 => {
+                if rest.len() < core::mem::size_of::<u32>() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let (len_bytes, rest) = rest.split_at(core::mem::size_of::<u32>());
+                let len =
+                    u32::from_le_bytes(unsafe { (len_bytes.as_ptr() as *const [u8; 4]).read() })
+                        as usize;
+
+                if rest.len() < len {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let (name, rest) = rest.split_at(len);
+
+                Ok((Some(Self::new(name)?), rest))
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    /// Seed taking the place of the entity id in the PDA.
+    pub fn id_seed(&self) -> &[u8; 8] {
+        self.id_seed
+    }
+
+    pub fn as_seed(&self) -> &[u8] {
+        &self.seed[..self.len]
+    }
+}
+
+pub const METADATA_KEY_LEN: usize = 8;
+
+pub const MAX_METADATA_LEN: usize = 1024;
+
+pub type MetadataKey = [u8; METADATA_KEY_LEN];
+
+pub struct MetadataEntry<'a> {
+    /// Offset of the entry within the metadata area.
+    pub offset: usize,
+    pub key: &'a MetadataKey,
+    pub value: &'a [u8],
+}
+
+impl MetadataEntry<'_> {
+    pub fn size(value_len: usize) -> usize {
+        METADATA_KEY_LEN + core::mem::size_of::<u16>() + value_len
+    }
+
+    pub fn len(&self) -> usize {
+        Self::size(self.value.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
+/// Iterates over inline metadata entries laid out as `[key][u16 len][value]`.
+pub struct EntityMetadata<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> EntityMetadata<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    pub fn get(self, key: &MetadataKey) -> Result<Option<MetadataEntry<'a>>, ProgramError> {
+        for entry in self {
+            let entry = entry?;
+            if entry.key == key {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> Iterator for EntityMetadata<'a> {
+    type Item = Result<MetadataEntry<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = &self.data[self.offset..];
+
+        if data.is_empty() {
+            return None;
+        }
+
+        if data.len() < MetadataEntry::size(0) {
+            return Some(Err(ProgramError::InvalidAccountData));
+        }
+
+        let (key, rest) = data.split_at(METADATA_KEY_LEN);
+        let value_len =
+            u16::from_le_bytes(unsafe { (rest.as_ptr() as *const [u8; 2]).read() }) as usize;
+
+        let Some(value) =
+            rest.get(core::mem::size_of::<u16>()..core::mem::size_of::<u16>() + value_len)
+        else {
+            return Some(Err(ProgramError::InvalidAccountData));
+        };
+
+        let entry = MetadataEntry {
+            offset: self.offset,
+            key: unsafe { &*(key.as_ptr() as *const MetadataKey) },
+            value,
+        };
+
+        self.offset += entry.len();
+
+        Some(Ok(entry))
     }
 }
 
@@ -92,3 +539,200 @@ impl AnchorAccount for Entity {
         self.discriminator
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_legacy_and_short_layouts() {
+        assert_eq!(
+            Entity::assert_current_layout(&[0; Entity::LEGACY_LEN]),
+            Err(WorldError::LegacyEntity.into())
+        );
+        assert_eq!(
+            Entity::assert_current_layout(&[0; Entity::LEN - 1]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert!(Entity::assert_current_layout(&[0; Entity::LEN]).is_ok());
+        assert!(Entity::assert_current_layout(&[0; Entity::LEN + 32]).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut entity: Entity = unsafe { core::mem::zeroed() };
+        assert!(entity.assert_version().is_err());
+
+        entity.version = Entity::VERSION;
+        assert!(entity.assert_version().is_ok());
+    }
+
+    #[test]
+    fn entity_kinds_round_trip() {
+        for kind in [EntityKind::Counter, EntityKind::Named, EntityKind::Nonce] {
+            let mut entity: Entity = unsafe { core::mem::zeroed() };
+            entity.init(1, 42, kind, &Pubkey::default()).unwrap();
+
+            assert_eq!(entity.kind(), Ok(kind));
+            assert_eq!(entity.id, 42);
+        }
+
+        assert_eq!(
+            EntityKind::try_from(3),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    fn borsh_name(name: &[u8]) -> Vec<u8> {
+        let mut data = vec![1];
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name);
+        data
+    }
+
+    #[test]
+    fn parse_unnamed() {
+        let (name, rest) = EntityName::parse(&[0, 7]).unwrap();
+        assert!(name.is_none());
+        assert_eq!(rest, &[7]);
+    }
+
+    #[test]
+    fn parse_short_name_is_raw() {
+        let mut data = borsh_name(b"player");
+        data.push(9);
+
+        let (name, rest) = EntityName::parse(&data).unwrap();
+        let name = name.unwrap();
+
+        assert_eq!(name.as_seed(), b"player");
+        assert_eq!(name.id_seed(), &NAMED_ENTITY_ID);
+        assert_eq!(rest, &[9]);
+    }
+
+    #[test]
+    fn parse_long_name_is_hashed() {
+        let long = [b'a'; MAX_SEED_LEN + 1];
+
+        let (name, _) = EntityName::parse(&borsh_name(&long)).unwrap();
+        let name = name.unwrap();
+
+        assert_eq!(name.as_seed(), hashv(&[&long]));
+        assert_eq!(name.id_seed(), &HASHED_NAME_ENTITY_ID);
+    }
+
+    #[test]
+    fn raw_name_equal_to_a_hash_does_not_collide() {
+        let long = [b'a'; MAX_SEED_LEN + 1];
+        let hashed = EntityName::new(&long).unwrap();
+        let raw = EntityName::new(&hashv(&[&long])).unwrap();
+
+        assert_eq!(raw.as_seed(), hashed.as_seed());
+        assert_ne!(raw.id_seed(), hashed.id_seed());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_names() {
+        // Bad option tag.
+        assert!(EntityName::parse(&[2]).is_err());
+        // Empty data.
+        assert!(EntityName::parse(&[]).is_err());
+        // Truncated length prefix.
+        assert!(EntityName::parse(&[1, 3, 0]).is_err());
+        // Length past the end of the data.
+        assert!(EntityName::parse(&[1, 4, 0, 0, 0, b'a']).is_err());
+        // Empty name.
+        assert!(EntityName::parse(&borsh_name(b"")).is_err());
+    }
+
+    fn metadata_entry(key: &MetadataKey, value: &[u8]) -> Vec<u8> {
+        let mut entry = key.to_vec();
+        entry.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        entry.extend_from_slice(value);
+        entry
+    }
+
+    #[test]
+    fn metadata_iterates_entries() {
+        let mut data = metadata_entry(b"name____", b"hero");
+        data.extend(metadata_entry(b"empty___", b""));
+        data.extend(metadata_entry(b"level___", &[9]));
+
+        let entries = EntityMetadata::new(&data)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].offset, 0);
+        assert_eq!(entries[0].value, b"hero");
+        assert_eq!(entries[1].offset, MetadataEntry::size(4));
+        assert!(entries[1].is_empty());
+        assert_eq!(
+            entries[2].offset,
+            MetadataEntry::size(4) + MetadataEntry::size(0)
+        );
+        assert_eq!(entries[2].value, &[9]);
+
+        let level = EntityMetadata::new(&data)
+            .get(b"level___")
+            .unwrap()
+            .unwrap();
+        assert_eq!(level.offset, entries[2].offset);
+        assert!(EntityMetadata::new(&data)
+            .get(b"missing_")
+            .unwrap()
+            .is_none());
+        assert!(EntityMetadata::new(&[]).next().is_none());
+    }
+
+    #[test]
+    fn metadata_rejects_truncated_entries() {
+        let data = metadata_entry(b"name____", b"hero");
+
+        // Header cut short.
+        let mut metadata = EntityMetadata::new(&data[..METADATA_KEY_LEN + 1]);
+        assert!(metadata.next().unwrap().is_err());
+
+        // Value shorter than its length prefix.
+        let mut metadata = EntityMetadata::new(&data[..data.len() - 1]);
+        assert!(metadata.next().unwrap().is_err());
+
+        // Valid entry followed by a truncated one.
+        let mut data = data.clone();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert!(EntityMetadata::new(&data).get(b"missing_").is_err());
+    }
+
+    #[test]
+    fn parse_expiry_slot() {
+        assert_eq!(Entity::parse_expiry_slot(&[]).unwrap(), 0);
+        assert_eq!(Entity::parse_expiry_slot(&[0]).unwrap(), 0);
+
+        let mut data = vec![1];
+        data.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(Entity::parse_expiry_slot(&data).unwrap(), 42);
+    }
+
+    #[test]
+    fn parse_expiry_slot_rejects_malformed() {
+        let mut data = vec![1];
+        data.extend_from_slice(&42u64.to_le_bytes());
+
+        assert!(Entity::parse_expiry_slot(&data[..data.len() - 1]).is_err());
+        data.push(0);
+        assert!(Entity::parse_expiry_slot(&data).is_err());
+        assert!(Entity::parse_expiry_slot(&[0, 0]).is_err());
+        assert!(Entity::parse_expiry_slot(&[2]).is_err());
+        assert!(Entity::parse_expiry_slot(&[1]).is_err());
+    }
+
+    #[test]
+    fn expiry() {
+        let mut entity: Entity = unsafe { core::mem::zeroed() };
+        assert!(!entity.is_expired(u64::MAX));
+
+        entity.expiry_slot = 10;
+        assert!(!entity.is_expired(9));
+        assert!(entity.is_expired(10));
+    }
+}
@@ -0,0 +1,173 @@
+use super::{
+    account::AnchorAccount,
+    transmutable::{Transmutable, TransmutableMut},
+};
+use crate::utils::resize_account;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+/// Ids of destroyed counter-based entities of a world, reused by `add_entity`
+/// before the world counter is bumped. The header is followed by `len` ids.
+#[repr(C)]
+pub struct FreeList {
+    pub discriminator: [u8; 8],
+    pub world: u64,
+    pub len: u64,
+}
+
+impl FreeList {
+    fn free_list_seed() -> &'static [u8] {
+        b"free_list"
+    }
+
+    pub fn pda(world_id: &[u8; 8]) -> (Pubkey, u8) {
+        find_program_address(&[Self::free_list_seed(), world_id], &crate::ID)
+    }
+
+    pub fn signer<'a>(world_id: &'a [u8; 8], bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        [
+            Self::free_list_seed().as_ref().into(),
+            world_id.as_ref().into(),
+            bump.as_ref().into(),
+        ]
+    }
+
+    pub fn init(&mut self, world: u64) -> Result<(), ProgramError> {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.world = world;
+        self.len = 0;
+        Ok(())
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let data = unsafe { account_info.borrow_mut_data_unchecked() };
+        let free_list = unsafe {
+            Self::load_mut_unchecked(
+                data.get_mut(..Self::LEN)
+                    .ok_or(ProgramError::InvalidAccountData)?,
+            )?
+        };
+        free_list.assert_account(account_info)?;
+        Ok(free_list)
+    }
+
+    pub fn size(&self) -> usize {
+        Self::LEN + self.len as usize * core::mem::size_of::<u64>()
+    }
+
+    /// Id the next counter-based `add_entity` will use given the world counter.
+    pub fn next_id(&self, data: &[u8], world_entities: u64) -> Result<u64, ProgramError> {
+        match self.len {
+            0 => Ok(world_entities),
+            _ => self.id_at(data, self.len as usize - 1),
+        }
+    }
+
+    fn id_at(&self, data: &[u8], index: usize) -> Result<u64, ProgramError> {
+        let offset = Self::LEN + index * core::mem::size_of::<u64>();
+        let bytes = data
+            .get(offset..offset + core::mem::size_of::<u64>())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(u64::from_le_bytes(unsafe {
+            (bytes.as_ptr() as *const [u8; 8]).read()
+        }))
+    }
+
+    pub fn push(
+        &mut self,
+        free_list_acct: &AccountInfo,
+        payer: &AccountInfo,
+        id: u64,
+    ) -> ProgramResult {
+        let size = self.size();
+
+        resize_account(free_list_acct, payer, size + core::mem::size_of::<u64>())?;
+
+        let data = unsafe { free_list_acct.borrow_mut_data_unchecked() };
+        data[size..size + core::mem::size_of::<u64>()].copy_from_slice(&id.to_le_bytes());
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    pub fn pop(
+        &mut self,
+        free_list_acct: &AccountInfo,
+        receiver: &AccountInfo,
+    ) -> Result<Option<u64>, ProgramError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+
+        let id = self.id_at(
+            unsafe { free_list_acct.borrow_data_unchecked() },
+            self.len as usize - 1,
+        )?;
+
+        self.len -= 1;
+
+        resize_account(free_list_acct, receiver, self.size())?;
+
+        Ok(Some(id))
+    }
+}
+
+impl TransmutableMut for FreeList {}
+
+impl Transmutable for FreeList {
+    const LEN: usize = core::mem::size_of::<FreeList>();
+}
+
+impl AnchorAccount for FreeList {
+    const DISCRIMINATOR: [u8; 8] = [23, 113, 68, 125, 185, 87, 85, 190];
+
+    fn discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn free_list(ids: &[u64]) -> (FreeList, Vec<u8>) {
+        let mut free_list: FreeList = unsafe { core::mem::zeroed() };
+        free_list.init(1).unwrap();
+        free_list.len = ids.len() as u64;
+
+        let mut data = vec![0; FreeList::LEN];
+        ids.iter().for_each(|id| data.extend(id.to_le_bytes()));
+
+        (free_list, data)
+    }
+
+    #[test]
+    fn next_id_reuses_the_last_freed_id() {
+        let (free_list, data) = free_list(&[4, 9]);
+
+        assert_eq!(free_list.size(), data.len());
+        assert_eq!(free_list.next_id(&data, 20).unwrap(), 9);
+        assert_eq!(free_list.id_at(&data, 0).unwrap(), 4);
+    }
+
+    #[test]
+    fn next_id_falls_back_to_the_world_counter() {
+        let (free_list, data) = free_list(&[]);
+
+        assert_eq!(free_list.next_id(&data, 20).unwrap(), 20);
+    }
+
+    #[test]
+    fn rejects_truncated_ids() {
+        let (free_list, data) = free_list(&[4, 9]);
+
+        assert!(free_list.next_id(&data[..data.len() - 1], 20).is_err());
+    }
+}
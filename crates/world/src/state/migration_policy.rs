@@ -0,0 +1,149 @@
+use super::{
+    account::AnchorAccount,
+    transmutable::{Transmutable, TransmutableMut},
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+/// Whether entities may leave or join a world through `migrate_entity`, and
+/// how many did.
+#[repr(C)]
+pub struct MigrationPolicy {
+    pub discriminator: [u8; 8],
+    pub world: u64,
+    pub migrated_in: u64,
+    pub migrated_out: u64,
+    pub flags: u8,
+}
+
+impl MigrationPolicy {
+    pub const ALLOW_IN: u8 = 1 << 0;
+    pub const ALLOW_OUT: u8 = 1 << 1;
+
+    fn migration_policy_seed() -> &'static [u8] {
+        b"migration_policy"
+    }
+
+    pub fn pda(world_id: &[u8; 8]) -> (Pubkey, u8) {
+        find_program_address(&[Self::migration_policy_seed(), world_id], &crate::ID)
+    }
+
+    pub fn signer<'a>(world_id: &'a [u8; 8], bump: &'a [u8; 1]) -> [Seed<'a>; 3] {
+        [
+            Self::migration_policy_seed().as_ref().into(),
+            world_id.as_ref().into(),
+            bump.as_ref().into(),
+        ]
+    }
+
+    pub fn init(&mut self, world: u64) -> Result<(), ProgramError> {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.world = world;
+        self.migrated_in = 0;
+        self.migrated_out = 0;
+        self.flags = 0;
+        Ok(())
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let policy = unsafe { Self::load_mut_unchecked(account_info.borrow_mut_data_unchecked())? };
+        policy.assert_account(account_info)?;
+        Ok(policy)
+    }
+
+    pub fn allows_in(&self) -> bool {
+        self.flags & Self::ALLOW_IN != 0
+    }
+
+    pub fn allows_out(&self) -> bool {
+        self.flags & Self::ALLOW_OUT != 0
+    }
+
+    pub fn set_flags(&mut self, flags: u8) -> Result<(), ProgramError> {
+        if flags & !(Self::ALLOW_IN | Self::ALLOW_OUT) != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        self.flags = flags;
+        Ok(())
+    }
+
+    /// Counts an entity migrating out of `self` into `target`.
+    pub fn record_migration(&mut self, target: &mut Self) -> Result<(), ProgramError> {
+        self.migrated_out = self
+            .migrated_out
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        target.migrated_in = target
+            .migrated_in
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+impl TransmutableMut for MigrationPolicy {}
+
+impl Transmutable for MigrationPolicy {
+    const LEN: usize = core::mem::size_of::<MigrationPolicy>();
+}
+
+impl AnchorAccount for MigrationPolicy {
+    const DISCRIMINATOR: [u8; 8] = [45, 198, 169, 43, 76, 226, 102, 80];
+
+    fn discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(world: u64) -> MigrationPolicy {
+        let mut policy: MigrationPolicy = unsafe { core::mem::zeroed() };
+        policy.init(world).unwrap();
+        policy
+    }
+
+    #[test]
+    fn sets_known_flags() {
+        let mut policy = policy(1);
+        assert!(!policy.allows_in() && !policy.allows_out());
+
+        policy.set_flags(MigrationPolicy::ALLOW_IN).unwrap();
+        assert!(policy.allows_in() && !policy.allows_out());
+
+        policy
+            .set_flags(MigrationPolicy::ALLOW_IN | MigrationPolicy::ALLOW_OUT)
+            .unwrap();
+        assert!(policy.allows_in() && policy.allows_out());
+
+        assert_eq!(
+            policy.set_flags(1 << 2),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert!(policy.allows_in() && policy.allows_out());
+    }
+
+    #[test]
+    fn records_migrations() {
+        let (mut source, mut target) = (policy(1), policy(2));
+
+        source.record_migration(&mut target).unwrap();
+        source.record_migration(&mut target).unwrap();
+        assert_eq!((source.migrated_out, target.migrated_in), (2, 2));
+        assert_eq!((source.migrated_in, target.migrated_out), (0, 0));
+
+        target.migrated_in = u64::MAX;
+        assert_eq!(
+            source.record_migration(&mut target),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+}
@@ -1,5 +1,10 @@
 pub mod account;
+pub mod archetype;
+pub mod compressed;
 pub mod entity;
+pub mod free_list;
+pub mod migration_policy;
+pub mod output_buffer;
 pub mod registry;
 pub mod system_whitelist;
 pub mod transmutable;
@@ -0,0 +1,136 @@
+use super::{
+    account::AnchorAccount,
+    transmutable::{Transmutable, TransmutableMut},
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+/// Record of the account a system writes large component states to for an
+/// authority. That account belongs to the system, so the world cannot clear
+/// it; instead the system stamps it with `sequence` before returning
+/// `OUTPUT_BUFFER` entries and the world bumps `sequence` once it read them,
+/// so a buffer left over from an earlier apply is rejected.
+#[repr(C)]
+pub struct OutputBuffer {
+    pub discriminator: [u8; 8],
+    pub system: Pubkey,
+    pub authority: Pubkey,
+    /// System-owned account holding `[sequence u64]` then the states.
+    pub buffer: Pubkey,
+    pub sequence: u64,
+}
+
+impl OutputBuffer {
+    /// Size of the sequence stamp at the start of the buffer.
+    pub const STAMP_LEN: usize = core::mem::size_of::<u64>();
+
+    fn output_buffer_seed() -> &'static [u8] {
+        b"output_buffer"
+    }
+
+    pub fn pda(system: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::output_buffer_seed(), system, authority], &crate::ID)
+    }
+
+    pub fn signer<'a>(
+        system: &'a Pubkey,
+        authority: &'a Pubkey,
+        bump: &'a [u8; 1],
+    ) -> [Seed<'a>; 4] {
+        [
+            Self::output_buffer_seed().as_ref().into(),
+            system.as_ref().into(),
+            authority.as_ref().into(),
+            bump.as_ref().into(),
+        ]
+    }
+
+    /// Address of the buffer `system` writes to for `authority`.
+    pub fn buffer_pda(system: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::output_buffer_seed(), authority], system)
+    }
+
+    pub fn init(&mut self, system: &Pubkey, authority: &Pubkey) -> Result<(), ProgramError> {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.system = *system;
+        self.authority = *authority;
+        self.buffer = Self::buffer_pda(system, authority).0;
+        self.sequence = 0;
+        Ok(())
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn from_account_info_mut(account_info: &AccountInfo) -> Result<&mut Self, ProgramError> {
+        let output_buffer =
+            unsafe { Self::load_mut_unchecked(account_info.borrow_mut_data_unchecked())? };
+        output_buffer.assert_account(account_info)?;
+        Ok(output_buffer)
+    }
+
+    /// Checks that `buffer` is the one of this record and carries the current
+    /// stamp, then bumps the sequence so it cannot be read again.
+    pub fn consume(&mut self, buffer: &AccountInfo) -> Result<(), ProgramError> {
+        if buffer.key() != &self.buffer || !buffer.is_owned_by(&self.system) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        self.assert_stamp(unsafe { buffer.borrow_data_unchecked() })?;
+
+        self.sequence = self
+            .sequence
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    fn assert_stamp(&self, data: &[u8]) -> Result<(), ProgramError> {
+        match data.get(..Self::STAMP_LEN) {
+            Some(stamp) if stamp == self.sequence.to_le_bytes() => Ok(()),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+impl TransmutableMut for OutputBuffer {}
+
+impl Transmutable for OutputBuffer {
+    const LEN: usize = core::mem::size_of::<OutputBuffer>();
+}
+
+impl AnchorAccount for OutputBuffer {
+    const DISCRIMINATOR: [u8; 8] = [79, 249, 123, 97, 3, 214, 180, 202];
+
+    fn discriminator(&self) -> [u8; 8] {
+        self.discriminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_current_stamp_only() {
+        let mut record: OutputBuffer = unsafe { core::mem::zeroed() };
+        record.sequence = 5;
+
+        let mut data = 5u64.to_le_bytes().to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert!(record.assert_stamp(&data).is_ok());
+        assert!(record
+            .assert_stamp(&data[..OutputBuffer::STAMP_LEN])
+            .is_ok());
+
+        // Left over from the previous apply.
+        data[..OutputBuffer::STAMP_LEN].copy_from_slice(&4u64.to_le_bytes());
+        assert!(record.assert_stamp(&data).is_err());
+
+        assert!(record.assert_stamp(&[5, 0, 0]).is_err());
+        assert!(record.assert_stamp(&[]).is_err());
+    }
+}
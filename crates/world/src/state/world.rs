@@ -2,6 +2,7 @@ use super::{
     account::AnchorAccount,
     transmutable::{Transmutable, TransmutableMut},
 };
+use crate::error::WorldError;
 use pinocchio::{
     account_info::AccountInfo,
     instruction::Seed,
@@ -32,6 +33,15 @@ impl World {
             bump.as_ref().into(),
         ]
     }
+
+    fn system_authority_seed() -> &'static [u8] {
+        b"system_authority"
+    }
+
+    /// PDA of `system` that signs when an approved system acts on entities directly.
+    pub fn system_authority(system: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::system_authority_seed()], system)
+    }
 }
 
 #[repr(C)]
@@ -125,6 +135,39 @@ impl<'a> WorldRef<'a> {
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
+
+    pub fn is_system_approved(&self, system: &Pubkey) -> Result<bool, ProgramError> {
+        Ok(self.permissionless()? || self.systems.binary_search(system).is_ok())
+    }
+
+    /// Checks that `authority` signed as `owner` or as the system authority of
+    /// a system approved by this world.
+    pub fn assert_entity_authority(
+        &self,
+        owner: &Pubkey,
+        authority: &AccountInfo,
+        system: Option<&AccountInfo>,
+    ) -> Result<(), ProgramError> {
+        if !authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if authority.key() == owner {
+            return Ok(());
+        }
+
+        let system = system.ok_or(WorldError::InvalidAuthority)?;
+
+        if !self.is_system_approved(system.key())? {
+            return Err(WorldError::SystemNotApproved.into());
+        }
+
+        if &World::system_authority(system.key()).0 != authority.key() {
+            return Err(WorldError::InvalidAuthority.into());
+        }
+
+        Ok(())
+    }
 }
 
 pub struct WorldMut<'a> {
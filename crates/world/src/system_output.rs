@@ -0,0 +1,691 @@
+use crate::{consts::MAX_UPDATE_DATA_LEN, error::WorldError, state::output_buffer::OutputBuffer};
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::{get_return_data, ReturnData},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+/// Return data of a system: a `u32` count followed by that many Borsh
+/// `Vec<u8>` component states, in the order the component pairs were passed,
+/// optionally followed by a `u32` count of `SystemCommand`s. A state's length
+/// prefix may instead be `OUTPUT_UNCHANGED` or carry `OUTPUT_PATCH`. States
+/// must fit in `MAX_UPDATE_DATA_LEN`, length prefix included, and patch lists
+/// without it.
+pub struct SystemOutput {
+    return_data: ReturnData,
+    commands_offset: usize,
+}
+
+impl SystemOutput {
+    /// Reads the return data left by `system` and checks it holds exactly
+    /// `components` well-formed entries and no commands.
+    pub fn get(system: &Pubkey, components: usize) -> Result<Self, ProgramError> {
+        let output = Self::get_with_commands(system, components)?;
+
+        if output.commands_offset != output.return_data.as_slice().len() {
+            return Err(WorldError::InvalidSystemOutput.into());
+        }
+
+        Ok(output)
+    }
+
+    /// Like `get`, but also accepts a well-formed list of commands after the
+    /// component entries.
+    pub fn get_with_commands(system: &Pubkey, components: usize) -> Result<Self, ProgramError> {
+        let return_data = get_return_data().ok_or(WorldError::InvalidSystemOutput)?;
+
+        if return_data.program_id() != system {
+            return Err(WorldError::InvalidSystemOutput.into());
+        }
+
+        let commands_offset = Self::validate(return_data.as_slice(), components)?;
+
+        Ok(Self {
+            return_data,
+            commands_offset,
+        })
+    }
+
+    /// Checks that `data` holds `components` well-formed entries and an
+    /// optional well-formed command list, returning where the commands start.
+    fn validate(data: &[u8], components: usize) -> Result<usize, ProgramError> {
+        let (count, rest) = read_u32(data).ok_or(WorldError::InvalidSystemOutput)?;
+
+        if count as usize != components {
+            return Err(WorldError::InvalidSystemOutput.into());
+        }
+
+        let mut entries = SystemOutputComponents { data: rest };
+
+        for _ in 0..components {
+            entries.next().ok_or(WorldError::InvalidSystemOutput)?;
+        }
+
+        let commands_offset = data.len() - entries.data.len();
+
+        if !entries.data.is_empty() {
+            let mut commands = SystemOutputCommands::parse(entries.data)?;
+
+            while commands.next().transpose()?.is_some() {}
+
+            if !commands.data.is_empty() {
+                return Err(WorldError::InvalidSystemOutput.into());
+            }
+        }
+
+        Ok(commands_offset)
+    }
+
+    /// Outputs of the components, in the order they were passed.
+    pub fn components(&self) -> SystemOutputComponents<'_> {
+        SystemOutputComponents {
+            data: &self.return_data.as_slice()[core::mem::size_of::<u32>()..],
+        }
+    }
+
+    /// Commands following the component entries, already checked to be well-formed.
+    pub fn commands(&self) -> SystemOutputCommands<'_> {
+        SystemOutputCommands::parse(&self.return_data.as_slice()[self.commands_offset..])
+            .unwrap_or_default()
+    }
+}
+
+pub struct SystemOutputComponents<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for SystemOutputComponents<'a> {
+    type Item = ComponentOutput<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (len, rest) = read_u32(self.data)?;
+
+        if len == OUTPUT_UNCHANGED {
+            self.data = rest;
+            return Some(ComponentOutput::Unchanged);
+        }
+
+        if len == OUTPUT_BUFFER {
+            let (offset, rest) = read_u32(rest)?;
+            let (len, rest) = read_u32(rest)?;
+
+            if (offset as usize) < OutputBuffer::STAMP_LEN {
+                return None;
+            }
+
+            self.data = rest;
+            return Some(ComponentOutput::Buffer { offset, len });
+        }
+
+        let size = (len & !OUTPUT_PATCH) as usize;
+
+        if rest.len() < size {
+            return None;
+        }
+
+        let (payload, rest) = rest.split_at(size);
+
+        let output = match len & OUTPUT_PATCH {
+            0 if core::mem::size_of::<u32>() + size <= MAX_UPDATE_DATA_LEN => {
+                ComponentOutput::State(&self.data[..core::mem::size_of::<u32>() + size])
+            }
+            0 => return None,
+            _ if size <= MAX_UPDATE_DATA_LEN && is_patch_list(payload) => {
+                ComponentOutput::Patch(payload)
+            }
+            _ => return None,
+        };
+
+        self.data = rest;
+
+        Some(output)
+    }
+}
+
+/// Length prefix of a component the system left unchanged, followed by no bytes.
+pub const OUTPUT_UNCHANGED: u32 = u32::MAX;
+
+/// Length prefix of a component whose new state the system wrote to the output
+/// buffer, followed by its `[offset u32][len u32]` there, past the stamp. The
+/// buffer must carry the current stamp of its `OutputBuffer` record.
+pub const OUTPUT_BUFFER: u32 = u32::MAX - 1;
+
+/// Bit set in the length prefix of a component the system patched, followed
+/// by a Borsh `Vec<(u32, Vec<u8>)>` of `(offset, bytes)` patches.
+pub const OUTPUT_PATCH: u32 = 1 << 31;
+
+pub enum ComponentOutput<'a> {
+    /// Borsh `Vec<u8>` of the new state, length prefix included.
+    State(&'a [u8]),
+    Unchanged,
+    /// Borsh `Vec<(u32, Vec<u8>)>` of patches.
+    Patch(&'a [u8]),
+    /// Range of the new state in the output buffer.
+    Buffer {
+        offset: u32,
+        len: u32,
+    },
+}
+
+impl ComponentOutput<'_> {
+    /// Writes the output back to `component`: no CPI when unchanged, an
+    /// `UpdatePatch` when patched, an `UpdateFromBuffer` when written to
+    /// `output_buffer` and an `Update` otherwise.
+    pub fn write_back(
+        &self,
+        authority: &AccountInfo,
+        component_program: &AccountInfo,
+        component: &AccountInfo,
+        instruction_sysvar_account: &AccountInfo,
+        session_token: Option<&AccountInfo>,
+        output_buffer: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        // The component program reads `output_buffer[offset..offset + len]`.
+        let buffer = |offset: u32, len: u32| {
+            let buffer = output_buffer.ok_or(WorldError::InvalidSystemOutput)?;
+
+            if offset as u64 + len as u64 > buffer.data_len() as u64 {
+                return Err::<_, ProgramError>(WorldError::InvalidSystemOutput.into());
+            }
+
+            Ok(buffer)
+        };
+
+        match (self, session_token) {
+            (ComponentOutput::Unchanged, _) => Ok(()),
+            (ComponentOutput::State(instruction_data), None) => {
+                hermes_cpi_interface::component::Update {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_data,
+                    instruction_sysvar_account,
+                }
+                .invoke()
+            }
+            (ComponentOutput::State(instruction_data), Some(session_token)) => {
+                hermes_cpi_interface::component::UpdateWithSession {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_data,
+                    instruction_sysvar_account,
+                    session_token,
+                }
+                .invoke()
+            }
+            (ComponentOutput::Patch(instruction_data), None) => {
+                hermes_cpi_interface::component::UpdatePatch {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_data,
+                    instruction_sysvar_account,
+                }
+                .invoke()
+            }
+            (ComponentOutput::Patch(instruction_data), Some(session_token)) => {
+                hermes_cpi_interface::component::UpdatePatchWithSession {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_data,
+                    instruction_sysvar_account,
+                    session_token,
+                }
+                .invoke()
+            }
+            (ComponentOutput::Buffer { offset, len }, None) => {
+                hermes_cpi_interface::component::UpdateFromBuffer {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_sysvar_account,
+                    buffer: buffer(*offset, *len)?,
+                    offset: *offset,
+                    len: *len,
+                }
+                .invoke()
+            }
+            (ComponentOutput::Buffer { offset, len }, Some(session_token)) => {
+                hermes_cpi_interface::component::UpdateFromBufferWithSession {
+                    authority,
+                    component,
+                    component_program: component_program.key(),
+                    instruction_sysvar_account,
+                    session_token,
+                    buffer: buffer(*offset, *len)?,
+                    offset: *offset,
+                    len: *len,
+                }
+                .invoke()
+            }
+        }
+    }
+}
+
+/// Whether `data` is exactly a Borsh `Vec<(u32, Vec<u8>)>`.
+fn is_patch_list(data: &[u8]) -> bool {
+    let Some((count, mut data)) = read_u32(data) else {
+        return false;
+    };
+
+    for _ in 0..count {
+        let Some((_offset, rest)) = read_u32(data) else {
+            return false;
+        };
+
+        let Some((len, rest)) = read_u32(rest) else {
+            return false;
+        };
+
+        let Some(rest) = rest.get(len as usize..) else {
+            return false;
+        };
+
+        data = rest;
+    }
+
+    data.is_empty()
+}
+
+/// Command a system asks the world to run after the write-back. Accounts are
+/// given as indices into the extra accounts of the apply.
+pub enum SystemCommand<'a> {
+    /// `[0][payer u8][entity u8][free_list u8][expiry_slot u64]`, as
+    /// `add_entity`. `free_list` is `NO_ACCOUNT` to take a new id.
+    SpawnEntity {
+        payer: u8,
+        entity: u8,
+        free_list: Option<u8>,
+        expiry_slot: u64,
+    },
+    /// `[1][payer u8][entity u8][component_program u8][component u8][system_program u8][data]`,
+    /// as `initialize_component`.
+    InitializeComponent {
+        payer: u8,
+        entity: u8,
+        component_program: u8,
+        component: u8,
+        system_program: u8,
+        data: &'a [u8],
+    },
+    /// `[2][receiver u8][component_program u8][component_program_data u8][entity u8][component u8][system_program u8]`,
+    /// as `destroy_component`.
+    DestroyComponent {
+        receiver: u8,
+        component_program: u8,
+        component_program_data: u8,
+        entity: u8,
+        component: u8,
+        system_program: u8,
+    },
+    /// `[3][data]`, logged with `sol_log_data`.
+    EmitEvent { data: &'a [u8] },
+}
+
+/// Account index standing for an omitted optional account in a command.
+pub const NO_ACCOUNT: u8 = u8::MAX;
+
+#[derive(Default)]
+pub struct SystemOutputCommands<'a> {
+    data: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> SystemOutputCommands<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let (count, data) = read_u32(data).ok_or(WorldError::InvalidSystemOutput)?;
+
+        Ok(Self {
+            data,
+            remaining: count,
+        })
+    }
+
+    fn accounts<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let accounts = self.data.get(..N)?.try_into().ok()?;
+        self.data = &self.data[N..];
+        Some(accounts)
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let (len, rest) = read_u32(self.data)?;
+
+        if rest.len() < len as usize {
+            return None;
+        }
+
+        let (bytes, rest) = rest.split_at(len as usize);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    fn parse_command(&mut self) -> Option<SystemCommand<'a>> {
+        let (tag, rest) = self.data.split_first()?;
+        self.data = rest;
+
+        match tag {
+            0 => {
+                let [payer, entity, free_list] = self.accounts()?;
+                Some(SystemCommand::SpawnEntity {
+                    payer,
+                    entity,
+                    free_list: (free_list != NO_ACCOUNT).then_some(free_list),
+                    expiry_slot: u64::from_le_bytes(self.accounts()?),
+                })
+            }
+            1 => {
+                let [payer, entity, component_program, component, system_program] =
+                    self.accounts()?;
+                Some(SystemCommand::InitializeComponent {
+                    payer,
+                    entity,
+                    component_program,
+                    component,
+                    system_program,
+                    data: self.bytes()?,
+                })
+            }
+            2 => {
+                let [receiver, component_program, component_program_data, entity, component, system_program] =
+                    self.accounts()?;
+                Some(SystemCommand::DestroyComponent {
+                    receiver,
+                    component_program,
+                    component_program_data,
+                    entity,
+                    component,
+                    system_program,
+                })
+            }
+            3 => Some(SystemCommand::EmitEvent {
+                data: self.bytes()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for SystemOutputCommands<'a> {
+    type Item = Result<SystemCommand<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        Some(
+            self.parse_command()
+                .ok_or(WorldError::InvalidSystemOutput.into()),
+        )
+    }
+}
+
+fn read_u32(data: &[u8]) -> Option<(u32, &[u8])> {
+    let bytes = data.get(..core::mem::size_of::<u32>())?;
+    let value = u32::from_le_bytes(unsafe { (bytes.as_ptr() as *const [u8; 4]).read() });
+    Some((value, &data[core::mem::size_of::<u32>()..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(bytes: &[u8]) -> Vec<u8> {
+        let mut entry = (bytes.len() as u32).to_le_bytes().to_vec();
+        entry.extend_from_slice(bytes);
+        entry
+    }
+
+    fn output(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = (entries.len() as u32).to_le_bytes().to_vec();
+        entries
+            .iter()
+            .for_each(|entry| data.extend_from_slice(entry));
+        data
+    }
+
+    #[test]
+    fn validates_states() {
+        let data = output(&[state(&[1, 2]), state(&[])]);
+
+        assert_eq!(SystemOutput::validate(&data, 2).unwrap(), data.len());
+
+        let mut components = SystemOutputComponents { data: &data[4..] };
+        assert!(
+            matches!(components.next(), Some(ComponentOutput::State(s)) if s == state(&[1, 2]))
+        );
+        assert!(matches!(components.next(), Some(ComponentOutput::State(s)) if s == state(&[])));
+        assert!(components.next().is_none());
+    }
+
+    #[test]
+    fn rejects_count_mismatch() {
+        let data = output(&[state(&[1])]);
+
+        assert!(SystemOutput::validate(&data, 0).is_err());
+        assert!(SystemOutput::validate(&data, 2).is_err());
+        assert!(SystemOutput::validate(&[], 0).is_err());
+        assert!(SystemOutput::validate(&[1, 0, 0], 1).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_state() {
+        let data = output(&[state(&[1, 2, 3])]);
+
+        assert!(SystemOutput::validate(&data[..data.len() - 1], 1).is_err());
+        assert!(SystemOutput::validate(&data[..6], 1).is_err());
+    }
+
+    #[test]
+    fn rejects_states_over_update_capacity() {
+        let max = output(&[state(&[7; MAX_UPDATE_DATA_LEN - 4])]);
+        assert!(SystemOutput::validate(&max, 1).is_ok());
+
+        let over = output(&[state(&[7; MAX_UPDATE_DATA_LEN - 3])]);
+        assert!(SystemOutput::validate(&over, 1).is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut data = output(&[state(&[1])]);
+        data.push(0);
+
+        assert!(SystemOutput::validate(&data, 1).is_err());
+    }
+
+    fn commands(commands: &[&[u8]]) -> Vec<u8> {
+        let mut data = (commands.len() as u32).to_le_bytes().to_vec();
+        commands
+            .iter()
+            .for_each(|command| data.extend_from_slice(command));
+        data
+    }
+
+    #[test]
+    fn parses_commands() {
+        let spawn: &[u8] = &[0, 1, 2, 3, 9, 0, 0, 0, 0, 0, 0, 0];
+        let spawn_new: &[u8] = &[0, 1, 2, NO_ACCOUNT, 0, 0, 0, 0, 0, 0, 0, 0];
+        let initialize: &[u8] = &[1, 1, 2, 3, 4, 5, 2, 0, 0, 0, 8, 9];
+        let destroy: &[u8] = &[2, 1, 2, 3, 4, 5, 6];
+        let event: &[u8] = &[3, 1, 0, 0, 0, 7];
+
+        let entries = output(&[state(&[1])]);
+        let mut data = entries.clone();
+        data.extend(commands(&[spawn, spawn_new, initialize, destroy, event]));
+
+        assert_eq!(SystemOutput::validate(&data, 1).unwrap(), entries.len());
+
+        let mut commands = SystemOutputCommands::parse(&data[entries.len()..]).unwrap();
+
+        assert!(matches!(
+            commands.next(),
+            Some(Ok(SystemCommand::SpawnEntity {
+                payer: 1,
+                entity: 2,
+                free_list: Some(3),
+                expiry_slot: 9,
+            }))
+        ));
+        assert!(matches!(
+            commands.next(),
+            Some(Ok(SystemCommand::SpawnEntity {
+                free_list: None,
+                expiry_slot: 0,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            commands.next(),
+            Some(Ok(SystemCommand::InitializeComponent {
+                payer: 1,
+                entity: 2,
+                component_program: 3,
+                component: 4,
+                system_program: 5,
+                data: [8, 9],
+            }))
+        ));
+        assert!(matches!(
+            commands.next(),
+            Some(Ok(SystemCommand::DestroyComponent {
+                receiver: 1,
+                component_program: 2,
+                component_program_data: 3,
+                entity: 4,
+                component: 5,
+                system_program: 6,
+            }))
+        ));
+        assert!(matches!(
+            commands.next(),
+            Some(Ok(SystemCommand::EmitEvent { data: [7] }))
+        ));
+        assert!(commands.next().is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_commands() {
+        let entries = output(&[]);
+        let spawn: &[u8] = &[0, 1, 2, NO_ACCOUNT, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        for malformed in [
+            commands(&[&[4]]),
+            commands(&[&[0, 1]]),
+            commands(&[&spawn[..spawn.len() - 1]]),
+            commands(&[&[1, 1, 2, 3, 4, 5, 2, 0, 0, 0, 8]]),
+            commands(&[&[3, 1, 0, 0]]),
+            commands(&[spawn, spawn])[..4 + spawn.len() + 3].to_vec(),
+            [commands(&[spawn]), vec![0]].concat(),
+            vec![1, 0],
+        ] {
+            let data = [entries.clone(), malformed].concat();
+            assert!(SystemOutput::validate(&data, 0).is_err());
+        }
+    }
+
+    fn patch(patches: &[(u32, &[u8])]) -> Vec<u8> {
+        let mut list = (patches.len() as u32).to_le_bytes().to_vec();
+        for (offset, bytes) in patches {
+            list.extend_from_slice(&offset.to_le_bytes());
+            list.extend_from_slice(&state(bytes));
+        }
+
+        let mut entry = (list.len() as u32 | OUTPUT_PATCH).to_le_bytes().to_vec();
+        entry.extend(list);
+        entry
+    }
+
+    #[test]
+    fn validates_unchanged_and_patches() {
+        let unchanged = OUTPUT_UNCHANGED.to_le_bytes().to_vec();
+        let patched = patch(&[(0, &[1, 2]), (8, &[])]);
+        let data = output(&[unchanged, patched.clone(), patch(&[])]);
+
+        assert_eq!(SystemOutput::validate(&data, 3).unwrap(), data.len());
+
+        let mut components = SystemOutputComponents { data: &data[4..] };
+        assert!(matches!(
+            components.next(),
+            Some(ComponentOutput::Unchanged)
+        ));
+        assert!(matches!(components.next(), Some(ComponentOutput::Patch(p)) if p == &patched[4..]));
+        assert!(matches!(components.next(), Some(ComponentOutput::Patch(p)) if p == [0; 4]));
+    }
+
+    #[test]
+    fn checks_patch_lists() {
+        let list = &patch(&[(4, &[1, 2, 3])])[4..];
+
+        assert!(is_patch_list(list));
+        assert!(is_patch_list(&[0; 4]));
+        assert!(!is_patch_list(&[]));
+        assert!(!is_patch_list(&list[..list.len() - 1]));
+        assert!(!is_patch_list(&[list, &[0]].concat()));
+        assert!(!is_patch_list(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn rejects_malformed_patches() {
+        // The list claims two patches but holds one.
+        let mut miscounted = patch(&[(0, &[1, 2])]);
+        miscounted[4] = 2;
+        assert!(SystemOutput::validate(&output(&[miscounted]), 1).is_err());
+
+        let max = patch(&[(0, &[7; MAX_UPDATE_DATA_LEN - 12])]);
+        assert!(SystemOutput::validate(&output(&[max]), 1).is_ok());
+
+        let over = patch(&[(0, &[7; MAX_UPDATE_DATA_LEN - 11])]);
+        assert!(SystemOutput::validate(&output(&[over]), 1).is_err());
+    }
+
+    fn buffered(offset: u32, len: u32) -> Vec<u8> {
+        [OUTPUT_BUFFER, offset, len]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn validates_buffer_markers() {
+        let data = output(&[buffered(8, 1000), state(&[1])]);
+
+        assert_eq!(SystemOutput::validate(&data, 2).unwrap(), data.len());
+
+        let mut components = SystemOutputComponents { data: &data[4..] };
+        assert!(matches!(
+            components.next(),
+            Some(ComponentOutput::Buffer {
+                offset: 8,
+                len: 1000
+            })
+        ));
+        assert!(matches!(components.next(), Some(ComponentOutput::State(_))));
+    }
+
+    #[test]
+    fn rejects_truncated_buffer_markers() {
+        let marker = buffered(8, 1000);
+
+        for len in [4, 8, marker.len() - 1] {
+            let data = output(&[marker[..len].to_vec()]);
+            assert!(SystemOutput::validate(&data, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_buffer_ranges_over_the_stamp() {
+        for offset in 0..OutputBuffer::STAMP_LEN as u32 {
+            let data = output(&[buffered(offset, 1000)]);
+            assert!(SystemOutput::validate(&data, 1).is_err());
+        }
+
+        let data = output(&[buffered(OutputBuffer::STAMP_LEN as u32, 1000)]);
+        assert!(SystemOutput::validate(&data, 1).is_ok());
+    }
+}
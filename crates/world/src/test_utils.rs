@@ -0,0 +1,107 @@
+//! Host-side accounts for tests, serialized the way the runtime hands them to
+//! the entrypoint.
+
+use crate::state::{
+    entity::{Entity, EntityKind},
+    transmutable::Transmutable,
+};
+use core::mem::MaybeUninit;
+use pinocchio::{
+    account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+    entrypoint::deserialize,
+    pubkey::Pubkey,
+};
+
+const MAX_ACCOUNTS: usize = 16;
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 1_000_000,
+            data,
+            is_signer: false,
+        }
+    }
+
+    /// Entity account of `world` listing `components` in its index.
+    pub fn entity(key: Pubkey, world: u64, components: &[Pubkey]) -> Self {
+        let mut entity: Entity = unsafe { core::mem::zeroed() };
+        entity
+            .init(world, 0, EntityKind::Counter, &Pubkey::default())
+            .unwrap();
+        entity.components = components.len() as u64;
+
+        let mut data =
+            unsafe { core::slice::from_raw_parts(&entity as *const _ as *const u8, Entity::LEN) }
+                .to_vec();
+        components.iter().for_each(|program| data.extend(program));
+
+        Self::new(key, crate::ID, data)
+    }
+}
+
+/// `AccountInfo`s pointing into a serialized input buffer they borrow from.
+pub struct TestAccounts {
+    _input: Vec<u64>,
+    infos: Vec<AccountInfo>,
+}
+
+impl TestAccounts {
+    pub fn new(accounts: &[TestAccount]) -> Self {
+        let mut bytes = (accounts.len() as u64).to_le_bytes().to_vec();
+
+        for account in accounts {
+            bytes.extend([u8::MAX, account.is_signer as u8, 1, 0, 0, 0, 0, 0]);
+            bytes.extend(account.key);
+            bytes.extend(account.owner);
+            bytes.extend(account.lamports.to_le_bytes());
+            bytes.extend((account.data.len() as u64).to_le_bytes());
+            bytes.extend(&account.data);
+            bytes.resize(
+                (bytes.len() + MAX_PERMITTED_DATA_INCREASE).next_multiple_of(8),
+                0,
+            );
+            bytes.extend(0u64.to_le_bytes());
+        }
+
+        bytes.extend(0u64.to_le_bytes());
+        bytes.extend(crate::ID);
+
+        let mut input = vec![0u64; bytes.len().div_ceil(8)];
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                input.as_mut_ptr() as *mut u8,
+                bytes.len(),
+            )
+        };
+
+        const UNINIT: MaybeUninit<AccountInfo> = MaybeUninit::uninit();
+        let mut infos = [UNINIT; MAX_ACCOUNTS];
+
+        let (_, count, _) =
+            unsafe { deserialize::<MAX_ACCOUNTS>(input.as_mut_ptr() as *mut u8, &mut infos) };
+
+        Self {
+            infos: infos[..count]
+                .iter()
+                .map(|info| unsafe { info.assume_init_ref() }.clone())
+                .collect(),
+            _input: input,
+        }
+    }
+
+    pub fn infos(&self) -> &[AccountInfo] {
+        &self.infos
+    }
+}
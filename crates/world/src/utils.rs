@@ -1,6 +1,11 @@
 use crate::consts::DISCRIMATOR_LENGTH;
 use core::mem::MaybeUninit;
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
 
 #[allow(clippy::type_complexity)]
 pub fn init_execute_cpi_accounts<'a>(
@@ -44,6 +49,23 @@ pub fn init_execute_cpi_accounts<'a>(
     Ok((components, separator_idx, remaining_accounts))
 }
 
+/// Writes references to `accounts` into `refs`, returning the filled prefix.
+pub fn account_refs<'a, 'b>(
+    accounts: impl Iterator<Item = &'a AccountInfo>,
+    refs: &'b mut [MaybeUninit<&'a AccountInfo>],
+) -> Result<&'b [&'a AccountInfo], ProgramError> {
+    let mut len = 0;
+
+    for account in accounts {
+        refs.get_mut(len)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .write(account);
+        len += 1;
+    }
+
+    Ok(unsafe { core::slice::from_raw_parts(refs.as_ptr() as *const &AccountInfo, len) })
+}
+
 pub fn assert_program_account(account_info: &AccountInfo) -> ProgramResult {
     if !account_info.is_owned_by(&crate::ID) {
         return Err(ProgramError::IllegalOwner);
@@ -68,3 +90,39 @@ pub fn assert_program_account_and_discriminator(
     assert_program_account(account_info)?;
     assert_discriminator(account_info, discriminator)
 }
+
+pub fn close_account(account_info: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    unsafe {
+        *destination.borrow_mut_lamports_unchecked() += account_info.lamports();
+        *account_info.borrow_mut_lamports_unchecked() = 0;
+    }
+
+    account_info.close()
+}
+
+/// Reallocs `account_info` to `new_size`, topping up rent from `payer` or
+/// refunding the excess to it.
+pub fn resize_account(
+    account_info: &AccountInfo,
+    payer: &AccountInfo,
+    new_size: usize,
+) -> ProgramResult {
+    let new_minimum_balance = Rent::get()?.minimum_balance(new_size);
+    let lamports = account_info.lamports();
+
+    if new_minimum_balance > lamports {
+        pinocchio_system::instructions::Transfer {
+            lamports: new_minimum_balance - lamports,
+            from: payer,
+            to: account_info,
+        }
+        .invoke()?;
+    } else if lamports > new_minimum_balance {
+        unsafe {
+            *account_info.borrow_mut_lamports_unchecked() -= lamports - new_minimum_balance;
+            *payer.borrow_mut_lamports_unchecked() += lamports - new_minimum_balance;
+        }
+    }
+
+    account_info.realloc(new_size, false)
+}